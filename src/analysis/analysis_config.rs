@@ -0,0 +1,376 @@
+//! The options of the analysis, as the dataflow core reads them.
+//!
+//! They are read through `AnalysisConfig`, so that the core does not depend on how they are set.
+//! `TaintConfig` implements it with the options of the `eval` feature, read from flags and `taint.toml` files.
+
+use std::sync::Arc;
+
+use crate::rvalue_model::RvalueModel;
+use crate::violation::Confidence;
+
+/// The options which the analysis of a body reads. Each of them is off, empty or the default unless overridden,
+/// so that an implementation only needs to provide those it sets.
+pub trait AnalysisConfig {
+    /// Whether `option` is enabled.
+    fn enabled(&self, _option: AnalysisOption) -> bool {
+        false
+    }
+
+    /// The paths configured in `list`.
+    fn paths(&self, _list: PathList) -> &[String] {
+        &[]
+    }
+
+    /// Whether the item at `path` matches one of the paths in `list`, as described for each of them.
+    fn matches(&self, list: PathList, path: &str) -> bool {
+        let mut paths = self.paths(list).iter();
+        match list {
+            PathList::Ignored => {
+                paths.any(|pattern| glob_match(pattern.as_bytes(), path.as_bytes()))
+            }
+            PathList::SanitizerBoundaries => paths.any(|boundary| {
+                path.strip_prefix(boundary.as_str())
+                    .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+            }),
+            PathList::SinkWriters | PathList::TaintedTypes => paths.any(|other| other == path),
+        }
+    }
+
+    /// The source configured for the function at `path`, if any.
+    fn source(&self, _path: &str) -> Option<&SourceSpec> {
+        None
+    }
+
+    /// The flow rules configured for the function at `path`, if any.
+    fn flow(&self, _path: &str) -> Option<&FlowSpec> {
+        None
+    }
+
+    /// How to treat calls to functions without a body to analyze.
+    fn unknown_call_policy(&self) -> UnknownCallPolicy {
+        UnknownCallPolicy::default()
+    }
+
+    /// What to assume about a recursive call, while the summary of the function it calls is computed.
+    fn recursion(&self) -> RecursionAssumption {
+        RecursionAssumption::default()
+    }
+
+    /// How deeply nested the summaries of called functions may be computed, if it is limited.
+    fn max_call_depth(&self) -> Option<usize> {
+        None
+    }
+
+    /// The lowest confidence of the violations which are reported, rather than counted as suppressed.
+    fn min_confidence(&self) -> Confidence {
+        Confidence::Low
+    }
+
+    /// Models for rvalues which the analysis does not handle itself, tried in order.
+    fn rvalue_models(&self) -> &[Arc<dyn RvalueModel>] {
+        &[]
+    }
+
+    /// Whether the tainted locals at each location of the function at `path` are recorded in `Shared::states`.
+    fn records_states(&self, _path: &str) -> bool {
+        false
+    }
+}
+
+/// An option which enables an additional check, or changes how taint propagates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisOption {
+    /// Report calls to `unwrap` or `expect` on a tainted `Option` or `Result`.
+    PanicOnTaintedIsSink,
+    /// Report indexing with a tainted index, since it may be out of bounds.
+    TaintedIndexIsSink,
+    /// Report panics whose message includes a tainted value.
+    PanicMessageIsSink,
+    /// Treat the functions which printing and logging macros call, and writing to sink writers, as sinks.
+    OutputSinks,
+    /// Consider the results of comparisons clean, even if their operands are tainted.
+    CleanComparisons,
+    /// Track implicit flows, where a value depends on a branch taken on tainted data.
+    ImplicitFlows,
+    /// Report sinks which are called in a branch taken on tainted data, even if their arguments are clean.
+    TaintedGuardIsSink,
+    /// Emit a note when a tainted value is cast between a pointer and an integer.
+    NotePointerCasts,
+    /// Attach the MIR location of each call to a sink to its diagnostic.
+    MirLocations,
+    /// Taint values received from channels which tainted values were sent through.
+    Channels,
+    /// Propagate the taint of an array or slice to its length.
+    TaintLen,
+    /// Consider every value read through a raw pointer tainted.
+    UnsafeConservative,
+    /// Assume that calls to functions without a body write tainted arguments through their reference arguments.
+    MutateArgsOnCall,
+    /// Record the MIR constructs which the analysis encountered, but does not handle.
+    ReportUnhandled,
+    /// Stop analyzing after the first violation.
+    FailFast,
+}
+
+/// A list of paths, which items are matched against in `AnalysisConfig::matches`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathList {
+    /// Patterns of functions which are not analyzed, where `*` matches any sequence.
+    Ignored,
+    /// Writer types which `write!` to is an output sink, besides those of the standard output and error.
+    SinkWriters,
+    /// Modules or types which validate everything passed through them, which match the items within them.
+    SanitizerBoundaries,
+    /// Types whose values are always tainted.
+    TaintedTypes,
+}
+
+/// A source function, and which of its outputs it taints.
+///
+/// It is written as `<path>` to taint the return value, or as `<path>(<outputs>)`,
+/// where `<outputs>` is a comma-separated list of `return` and indices of reference arguments,
+/// whose referents are tainted. For example, `std::io::Stdin::read_line(1)` taints the buffer
+/// passed to `read_line`, but not the number of bytes it returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpec {
+    pub path: String,
+    /// Whether the return value is tainted.
+    pub taints_return: bool,
+    /// Indices of the arguments whose referents are tainted.
+    pub out_args: Vec<usize>,
+}
+
+impl SourceSpec {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (path, outputs) = match value.strip_suffix(')') {
+            Some(rest) => rest.split_once('(')?,
+            None => {
+                return Some(SourceSpec {
+                    path: value.to_owned(),
+                    taints_return: true,
+                    out_args: Vec::new(),
+                })
+            }
+        };
+
+        let mut spec = SourceSpec {
+            path: path.to_owned(),
+            taints_return: false,
+            out_args: Vec::new(),
+        };
+        for output in outputs.split(',').map(str::trim) {
+            if output == "return" {
+                spec.taints_return = true;
+            } else {
+                spec.out_args.push(output.parse().ok()?);
+            }
+        }
+        Some(spec)
+    }
+}
+
+/// Rules for how taint flows through calls to a function.
+///
+/// It is written as `<path>(<rules>)`, where `<rules>` is a comma-separated list of
+/// `arg<n> -> ret`, which taints the return value if argument `n` is tainted,
+/// and `arg<n> -> *arg<m>`, which taints the referent of argument `m` if argument `n` is tainted.
+/// For example, `std::ptr::copy(arg0 -> *arg1)` models a copy into the second argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowSpec {
+    pub path: String,
+    pub flows: Vec<Flow>,
+}
+
+/// The taint of argument `from` flows to `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flow {
+    pub from: usize,
+    pub to: FlowTarget,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowTarget {
+    /// The return value.
+    Return,
+    /// The referent of the argument with the given index.
+    Arg(usize),
+}
+
+impl FlowSpec {
+    pub fn parse(value: &str) -> Option<Self> {
+        let (path, rules) = value.strip_suffix(')')?.split_once('(')?;
+        let flows = rules
+            .split(',')
+            .map(|rule| {
+                let (from, to) = rule.split_once("->")?;
+                let from = from.trim().strip_prefix("arg")?.parse().ok()?;
+                let to = match to.trim() {
+                    "ret" => FlowTarget::Return,
+                    to => FlowTarget::Arg(to.strip_prefix("*arg")?.parse().ok()?),
+                };
+                Some(Flow { from, to })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(FlowSpec {
+            path: path.to_owned(),
+            flows,
+        })
+    }
+}
+
+/// How to treat calls to functions without a body to analyze,
+/// such as foreign functions and trait methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownCallPolicy {
+    /// The return value is tainted if any argument is tainted.
+    PropagateArgsToReturn,
+    /// The return value is always tainted.
+    TaintReturn,
+    /// The return value is clean, even where it overwrites a tainted value.
+    Ignore,
+}
+
+impl Default for UnknownCallPolicy {
+    fn default() -> Self {
+        UnknownCallPolicy::PropagateArgsToReturn
+    }
+}
+
+/// What a recursive call returns while the summary of the function it calls is computed.
+/// The summary is computed again with the result as the new assumption, until it no longer changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecursionAssumption {
+    /// Recursive calls leave their arguments as they are, and return a clean value.
+    /// This finds the least summary, but taint which only a recursive call would introduce,
+    /// such as through a path the summaries do not describe, may be missed.
+    Optimistic,
+    /// Recursive calls return a tainted value if any of their arguments is tainted.
+    /// This may report flows which do not exist, but does not depend on the iteration to find them.
+    Pessimistic,
+}
+
+impl Default for RecursionAssumption {
+    fn default() -> Self {
+        RecursionAssumption::Optimistic
+    }
+}
+
+/// Whether `text` matches `pattern`, where `*` matches any sequence of bytes.
+///
+/// On a mismatch, only the last `*` is retried with one more byte, since any earlier one
+/// could only match less of the text, so this takes linear time in most cases and
+/// quadratic time at worst.
+pub(crate) fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The position after the last `*`, and the position in `text` it is retried from.
+    let mut retry = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                p += 1;
+                retry = Some((p, t));
+            }
+            Some(&c) if c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match retry {
+                Some((star, from)) => {
+                    p = star;
+                    t = from + 1;
+                    retry = Some((star, from + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_source_spec() {
+        assert_eq!(
+            SourceSpec::parse("std::env::var"),
+            Some(SourceSpec {
+                path: "std::env::var".to_owned(),
+                taints_return: true,
+                out_args: vec![],
+            })
+        );
+        assert_eq!(
+            SourceSpec::parse("std::io::Stdin::read_line(1)"),
+            Some(SourceSpec {
+                path: "std::io::Stdin::read_line".to_owned(),
+                taints_return: false,
+                out_args: vec![1],
+            })
+        );
+        assert_eq!(
+            SourceSpec::parse("recv_into(return, 0, 2)"),
+            Some(SourceSpec {
+                path: "recv_into".to_owned(),
+                taints_return: true,
+                out_args: vec![0, 2],
+            })
+        );
+        assert_eq!(SourceSpec::parse("read_line(buf)"), None);
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match(b"std::env::*", b"std::env::var"));
+        assert!(glob_match(b"*::read*", b"std::fs::read_to_string"));
+        assert!(glob_match(b"a*b*c", b"aXbYbZc"));
+        assert!(glob_match(b"**", b""));
+        assert!(!glob_match(b"std::env::*", b"std::fs::read"));
+        assert!(!glob_match(b"a*b*c", b"aXbYbZ"));
+        assert!(!glob_match(b"", b"a"));
+
+        // Many stars on a long path which does not match must not backtrack exponentially.
+        let pattern = "*a".repeat(30) + "b";
+        let path = "a".repeat(200);
+        assert!(!glob_match(pattern.as_bytes(), path.as_bytes()));
+    }
+
+    #[test]
+    fn parse_flow_spec() {
+        assert_eq!(
+            FlowSpec::parse("std::ptr::copy(arg0 -> *arg1)"),
+            Some(FlowSpec {
+                path: "std::ptr::copy".to_owned(),
+                flows: vec![Flow {
+                    from: 0,
+                    to: FlowTarget::Arg(1),
+                }],
+            })
+        );
+        assert_eq!(
+            FlowSpec::parse("merge(arg0->ret,arg1 -> ret, arg1 -> *arg2)"),
+            Some(FlowSpec {
+                path: "merge".to_owned(),
+                flows: vec![
+                    Flow {
+                        from: 0,
+                        to: FlowTarget::Return,
+                    },
+                    Flow {
+                        from: 1,
+                        to: FlowTarget::Return,
+                    },
+                    Flow {
+                        from: 1,
+                        to: FlowTarget::Arg(2),
+                    },
+                ],
+            })
+        );
+        assert_eq!(FlowSpec::parse("merge"), None);
+        assert_eq!(FlowSpec::parse("merge(arg0 -> arg1)"), None);
+        assert_eq!(FlowSpec::parse("merge(ret -> *arg1)"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{BTreeSet, HashMap};
+
 use hir::itemlikevisit::ItemLikeVisitor;
 use rustc_ast::AttrKind;
 use rustc_hir as hir;
@@ -18,6 +20,29 @@ pub struct AttrInfo {
     pub sources: Vec<DefId>,
     pub sinks: Vec<DefId>,
     pub sanitizers: Vec<DefId>,
+    /// The categories a source or sink is tagged with, such as `sql` in `#[taint::sink(sql)]`.
+    /// A source produces values of its categories, and a sink only cares about values of its own.
+    /// Sources and sinks without categories match all others.
+    pub categories: HashMap<DefId, Vec<String>>,
+}
+
+/// The sources and sinks which are matched while analyzing the crate.
+///
+/// Taint is a single bit, so the flows of each category are found in a separate round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryFilter {
+    /// All sources and sinks, when no sink has categories.
+    Any,
+    /// All sources, and the sinks without categories.
+    Uncategorized,
+    /// The sources without categories or of this category, and the sinks of this category.
+    Only(String),
+}
+
+impl Default for CategoryFilter {
+    fn default() -> Self {
+        CategoryFilter::Any
+    }
 }
 
 #[derive(Debug)]
@@ -39,6 +64,51 @@ impl AttrInfo {
             None
         }
     }
+
+    fn categories(&self, id: &DefId) -> &[String] {
+        self.categories.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether the values of the source `id` are tainted in the round of `filter`.
+    pub fn source_matches(&self, id: &DefId, filter: &CategoryFilter) -> bool {
+        let categories = self.categories(id);
+        match filter {
+            CategoryFilter::Any | CategoryFilter::Uncategorized => true,
+            CategoryFilter::Only(category) => {
+                categories.is_empty() || categories.contains(category)
+            }
+        }
+    }
+
+    /// Whether the sink `id` is checked in the round of `filter`.
+    pub fn sink_matches(&self, id: &DefId, filter: &CategoryFilter) -> bool {
+        let categories = self.categories(id);
+        match filter {
+            CategoryFilter::Any => true,
+            CategoryFilter::Uncategorized => categories.is_empty(),
+            CategoryFilter::Only(category) => categories.contains(category),
+        }
+    }
+
+    /// The rounds which together check every sink against the sources it cares about.
+    pub fn category_filters(&self) -> Vec<CategoryFilter> {
+        let categories = self
+            .sinks
+            .iter()
+            .flat_map(|id| self.categories(id))
+            .collect::<BTreeSet<_>>();
+        if categories.is_empty() {
+            return vec![CategoryFilter::Any];
+        }
+
+        std::iter::once(CategoryFilter::Uncategorized)
+            .chain(
+                categories
+                    .into_iter()
+                    .map(|category| CategoryFilter::Only(category.clone())),
+            )
+            .collect()
+    }
 }
 
 impl<'tcx> TaintAttributeFinder<'tcx> {
@@ -60,6 +130,17 @@ impl TaintAttributeFinder<'_> {
         for attr in attrs {
             if let AttrKind::Normal(ref item, _) = attr.kind {
                 if let Some(symbol) = get_taint_attr(item) {
+                    let categories = attr
+                        .meta_item_list()
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(|category| category.ident())
+                        .map(|category| category.to_string())
+                        .collect::<Vec<_>>();
+                    if !categories.is_empty() {
+                        self.info.categories.insert(def_id, categories);
+                    }
+
                     if symbol == &sym_source {
                         self.info.sources.push(def_id)
                     } else if symbol == &sym_sink {
@@ -0,0 +1,105 @@
+//! Which blocks of a body run depending on which branch of a `SwitchInt` is taken,
+//! found from the blocks which post-dominate it.
+
+use std::collections::HashSet;
+
+use rustc_index::{bit_set::BitSet, vec::IndexVec};
+use rustc_middle::mir::{traversal::postorder, BasicBlock, Body, TerminatorKind};
+
+/// For each block of `body`, the blocks which every path from it to a return goes through, including itself.
+pub(crate) type PostDominators = IndexVec<BasicBlock, BitSet<BasicBlock>>;
+
+/// The successors of `block` when it completes normally. Unwinding is left out, so that the blocks
+/// after a call in a branch still post-dominate it, and so are the edges of `FalseEdge` and `FalseUnwind`
+/// which are never taken.
+fn successors(body: &Body<'_>, block: BasicBlock) -> Vec<BasicBlock> {
+    match &body[block].terminator().kind {
+        TerminatorKind::Call { destination, .. } => {
+            destination.iter().map(|(_, target)| *target).collect()
+        }
+        TerminatorKind::Drop { target, .. }
+        | TerminatorKind::DropAndReplace { target, .. }
+        | TerminatorKind::Assert { target, .. } => vec![*target],
+        TerminatorKind::FalseEdge { real_target, .. }
+        | TerminatorKind::FalseUnwind { real_target, .. } => vec![*real_target],
+        TerminatorKind::Yield { resume, .. } => vec![*resume],
+        TerminatorKind::InlineAsm { destination, .. } => destination.iter().copied().collect(),
+        kind => kind.successors().copied().collect(),
+    }
+}
+
+/// The post-dominators of each block of `body`, as the greatest solution of the usual equations,
+/// which joins the post-dominators of the successors of a block by intersection.
+pub(crate) fn post_dominators(body: &Body<'_>) -> PostDominators {
+    let blocks = body.basic_blocks().len();
+    let successors = body
+        .basic_blocks()
+        .indices()
+        .map(|block| successors(body, block))
+        .collect::<IndexVec<BasicBlock, _>>();
+
+    let mut post_dominators = successors
+        .iter_enumerated()
+        .map(|(block, successors)| {
+            if successors.is_empty() {
+                let mut only = BitSet::new_empty(blocks);
+                only.insert(block);
+                only
+            } else {
+                BitSet::new_filled(blocks)
+            }
+        })
+        .collect::<PostDominators>();
+
+    // Successors come before their predecessors in postorder, except around loops.
+    let order = postorder(body).map(|(block, _)| block).collect::<Vec<_>>();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &block in order.iter() {
+            let (first, rest) = match successors[block].split_first() {
+                Some(split) => split,
+                None => continue,
+            };
+            let mut joined = post_dominators[*first].clone();
+            for successor in rest {
+                joined.intersect(&post_dominators[*successor]);
+            }
+            joined.insert(block);
+            if joined != post_dominators[block] {
+                post_dominators[block] = joined;
+                changed = true;
+            }
+        }
+    }
+
+    // Blocks which never return, such as those of an endless loop, would be post-dominated by every block,
+    // so take them to be post-dominated by none but themselves.
+    for (block, dominators) in post_dominators.iter_enumerated_mut() {
+        if dominators.count() == blocks && blocks > 1 {
+            dominators.clear();
+            dominators.insert(block);
+        }
+    }
+    post_dominators
+}
+
+/// The blocks which only run depending on which target `switch` branches to:
+/// those reachable from its targets before the branches join again, at a block which post-dominates `switch`.
+pub(crate) fn control_dependent(
+    body: &Body<'_>,
+    switch: BasicBlock,
+    post_dominators: &PostDominators,
+) -> HashSet<BasicBlock> {
+    let join = &post_dominators[switch];
+    let mut dependent = HashSet::new();
+    let mut worklist = successors(body, switch);
+
+    while let Some(block) = worklist.pop() {
+        if join.contains(block) || !dependent.insert(block) {
+            continue;
+        }
+        worklist.extend(successors(body, block));
+    }
+    dependent
+}
@@ -0,0 +1,62 @@
+//! Instrumentation of the fixpoint iteration, for debugging transfer functions.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rustc_middle::mir::BasicBlock;
+
+/// The number of tainted locals at the end of each block, every time the fixpoint iteration visits it.
+///
+/// The transfer function must be monotone for the iteration to converge,
+/// so the tainted set of a block should never shrink between two visits.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConvergenceTrace {
+    pub blocks: BTreeMap<BasicBlock, Vec<usize>>,
+}
+
+impl ConvergenceTrace {
+    pub(crate) fn record(&mut self, block: BasicBlock, tainted: usize) {
+        self.blocks.entry(block).or_default().push(tainted);
+    }
+
+    /// The largest number of times a block was visited.
+    pub fn iterations(&self) -> usize {
+        self.blocks.values().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Blocks whose tainted set shrank between two visits.
+    pub fn non_monotone_blocks(&self) -> Vec<BasicBlock> {
+        self.blocks
+            .iter()
+            .filter(|(_, sizes)| sizes.windows(2).any(|pair| pair[1] < pair[0]))
+            .map(|(block, _)| *block)
+            .collect()
+    }
+}
+
+impl fmt::Display for ConvergenceTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (block, sizes) in &self.blocks {
+            let sizes = sizes.iter().map(usize::to_string).collect::<Vec<_>>();
+            writeln!(f, "{:?}: {}", block, sizes.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinking_block_is_not_monotone() {
+        let mut trace = ConvergenceTrace::default();
+        for (block, tainted) in &[(0, 1), (1, 1), (1, 3), (2, 2), (2, 1)] {
+            trace.record(BasicBlock::from_usize(*block), *tainted);
+        }
+
+        assert_eq!(trace.iterations(), 2);
+        assert_eq!(trace.non_monotone_blocks(), vec![BasicBlock::from_usize(2)]);
+        assert_eq!(trace.to_string(), "bb0: 1\nbb1: 1 3\nbb2: 2 1\n");
+    }
+}
@@ -1,13 +1,34 @@
+use rustc_errors::{DiagnosticBuilder, DiagnosticId};
 use rustc_macros::SessionDiagnostic;
+use rustc_session::{Session, SessionDiagnostic};
 use rustc_span::Span;
 
-#[derive(SessionDiagnostic)]
-#[error = "T0001"]
+/// Points at the tainted argument, and at the source of its taint if it is known.
 pub(crate) struct TaintedSink {
     pub fn_name: String,
-    #[message = "function `{fn_name}` received tainted input"]
-    #[label = "sink function"]
     pub span: Span,
+    pub origin: Option<Span>,
+    /// The MIR location of the call, and the span of its terminator, with `--taint-mir-locations`.
+    pub mir_location: Option<(String, Span)>,
+}
+
+impl<'a> SessionDiagnostic<'a> for TaintedSink {
+    fn into_diagnostic(self, sess: &'a Session) -> DiagnosticBuilder<'a> {
+        let msg = format!("function `{}` received tainted input", self.fn_name);
+        let mut diag = sess.struct_span_err_with_code(
+            self.span,
+            &msg,
+            DiagnosticId::Error("T0001".to_owned()),
+        );
+        diag.span_label(self.span, "reaches sink here");
+        if let Some(origin) = self.origin {
+            diag.span_label(origin, "tainted here");
+        }
+        if let Some((location, span)) = self.mir_location {
+            diag.span_note(span, &format!("sink called at MIR location {}", location));
+        }
+        diag
+    }
 }
 
 #[derive(SessionDiagnostic)]
@@ -18,3 +39,53 @@ pub(crate) struct InvalidVariant {
     #[label = "invalid taint attribute"]
     pub span: Span,
 }
+
+#[derive(SessionDiagnostic)]
+#[error = "T0004"]
+pub(crate) struct TaintedPanic {
+    pub fn_name: String,
+    #[message = "`{fn_name}` may panic on tainted input"]
+    #[label = "may panic"]
+    pub span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error = "T0005"]
+pub(crate) struct TaintedGuard {
+    pub fn_name: String,
+    #[message = "function `{fn_name}` is called depending on tainted input"]
+    #[label = "sink function"]
+    pub span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error = "T0006"]
+pub(crate) struct TaintedIndex {
+    #[message = "tainted index used in bounds-checked access"]
+    #[label = "tainted index"]
+    pub span: Span,
+}
+
+#[derive(SessionDiagnostic)]
+#[error = "T0007"]
+pub(crate) struct TaintedPanicMessage {
+    #[message = "tainted value formatted into a panic message"]
+    #[label = "may be written to the output"]
+    pub span: Span,
+}
+
+/// Emitted as a warning, since returning tainted data is not an error by itself.
+pub(crate) struct TaintedReturn {
+    pub fn_name: String,
+    pub span: Span,
+}
+
+impl<'a> SessionDiagnostic<'a> for TaintedReturn {
+    fn into_diagnostic(self, sess: &'a Session) -> DiagnosticBuilder<'a> {
+        let msg = format!("public function `{}` may return tainted data", self.fn_name);
+        let mut diag = sess.struct_span_warn(self.span, &msg);
+        diag.code(DiagnosticId::Error("T0003".to_owned()));
+        diag.span_label(self.span, "returns tainted data");
+        diag
+    }
+}
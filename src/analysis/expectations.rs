@@ -0,0 +1,197 @@
+//! Expected results of the analysis, written as annotations in the program it analyzes,
+//! and their comparison with the violations it reports.
+//!
+//! An annotation is a comment at the end of the line it applies to:
+//! `//~ VIOLATION sink=<sink>` expects a call to `<sink>` on that line to receive tainted input,
+//! where `<sink>` is the name the violation is reported with, such as `output` or `Command::arg`,
+//! and `//~ TAINT source` expects the tainted input of a violation to come from that line.
+//! The results match when every annotation is matched by a violation, and every violation by annotations.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+
+use crate::report::CrateReport;
+
+const MARKER: &str = "//~";
+
+/// What an annotation expects of the line it is on.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Expectation {
+    /// A call to `sink` on `line` receives tainted input.
+    Violation { line: usize, sink: String },
+    /// The tainted input of a violation comes from `line`, such as from a call to a source.
+    Source { line: usize },
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expectation::Violation { line, sink } => {
+                write!(f, "line {}: VIOLATION sink={}", line, sink)
+            }
+            Expectation::Source { line } => write!(f, "line {}: TAINT source", line),
+        }
+    }
+}
+
+/// The expectations annotated in `program`, whose lines are numbered from 1.
+/// Returns an error for annotations which are not understood, rather than ignoring them.
+pub fn parse(program: &str) -> Result<BTreeSet<Expectation>, String> {
+    let mut expectations = BTreeSet::new();
+    for (index, text) in program.lines().enumerate() {
+        let line = index + 1;
+        let annotation = match text.split_once(MARKER) {
+            Some((_, annotation)) => annotation.trim(),
+            None => continue,
+        };
+
+        let expectation = match annotation.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["VIOLATION", sink] => match sink.strip_prefix("sink=") {
+                Some(sink) if !sink.is_empty() => Expectation::Violation {
+                    line,
+                    sink: sink.to_owned(),
+                },
+                _ => return Err(format!("line {}: expected `sink=<sink>`", line)),
+            },
+            ["TAINT", "source"] => Expectation::Source { line },
+            _ => {
+                return Err(format!(
+                    "line {}: unknown annotation `{} {}`",
+                    line, MARKER, annotation
+                ))
+            }
+        };
+        expectations.insert(expectation);
+    }
+    Ok(expectations)
+}
+
+/// What the violations of `report` in `file` would be annotated with.
+/// Violations in other files, and sources in other files, are left out.
+pub fn found(report: &CrateReport, file: &str) -> BTreeSet<Expectation> {
+    let line_in_file = |location: &str| {
+        let mut parts = location.rsplitn(3, ':');
+        let (_column, line) = (parts.next(), parts.next());
+        match parts.next() {
+            Some(name) if name == file => line.and_then(|line| line.parse().ok()),
+            _ => None,
+        }
+    };
+
+    let mut found = BTreeSet::new();
+    for violation in report.violations.iter() {
+        if let Some(line) = line_in_file(&violation.location) {
+            found.insert(Expectation::Violation {
+                line,
+                sink: violation.sink.clone(),
+            });
+        }
+        if let Some(line) = violation.source.as_deref().and_then(line_in_file) {
+            found.insert(Expectation::Source { line });
+        }
+    }
+    found
+}
+
+/// The differences between the `expected` and `found` results, one per line,
+/// prefixed by `missing` or `unexpected`. Empty if they match.
+pub fn compare(expected: &BTreeSet<Expectation>, found: &BTreeSet<Expectation>) -> String {
+    let mut text = String::new();
+    for expectation in expected.difference(found) {
+        let _ = writeln!(text, "missing {}", expectation);
+    }
+    for expectation in found.difference(expected) {
+        let _ = writeln!(text, "unexpected {}", expectation);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+    use crate::eval::config::TaintConfig;
+    use crate::test_utils::analyze;
+
+    /// The annotated programs under `tests/expected`, in a stable order.
+    fn programs() -> Vec<PathBuf> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expected");
+        let mut programs = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "rs"))
+            .collect::<Vec<_>>();
+        programs.sort();
+        programs
+    }
+
+    #[test]
+    fn parse_annotations() {
+        let expected = parse(
+            "let value = input(); //~ TAINT source\n\
+             \n\
+             output(value); //~ VIOLATION sink=output\n",
+        );
+        assert_eq!(
+            expected.unwrap().into_iter().collect::<Vec<_>>(),
+            vec![
+                Expectation::Violation {
+                    line: 3,
+                    sink: "output".to_owned()
+                },
+                Expectation::Source { line: 1 },
+            ]
+        );
+
+        assert_eq!(
+            parse("output(value); //~ VIOLATION output"),
+            Err("line 1: expected `sink=<sink>`".to_owned())
+        );
+        assert_eq!(
+            parse("output(value); //~ ERROR tainted"),
+            Err("line 1: unknown annotation `//~ ERROR tainted`".to_owned())
+        );
+    }
+
+    #[test]
+    fn compare_expectations() {
+        let expected = parse("output(value); //~ VIOLATION sink=output\nlog(value);").unwrap();
+        let found = vec![Expectation::Violation {
+            line: 2,
+            sink: "log".to_owned(),
+        }]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            compare(&expected, &found),
+            "missing line 1: VIOLATION sink=output\nunexpected line 2: VIOLATION sink=log\n"
+        );
+        assert_eq!(compare(&found, &found), "");
+    }
+
+    #[test]
+    fn annotated_programs() {
+        let programs = programs();
+        assert!(programs.len() >= 3, "missing annotated programs");
+
+        let mut failures = String::new();
+        for program in programs {
+            let source = std::fs::read_to_string(&program).unwrap();
+            let expected = parse(&source).unwrap();
+            let report = analyze(&source, TaintConfig::default());
+
+            let differences = compare(&expected, &found(&report, "<program.rs>"));
+            if !differences.is_empty() {
+                let _ = write!(failures, "{}:\n{}", program.display(), differences);
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "the violations differ from the annotations\n{}",
+            failures
+        );
+    }
+}
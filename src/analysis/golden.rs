@@ -0,0 +1,128 @@
+//! Golden tests, which pin the tainted locals before every statement and terminator
+//! of the function `analyzed` in each program under `tests/golden`.
+//!
+//! The first argument of `analyzed` is tainted, and the others are clean.
+//! The expected states are checked in next to each program, in a `.taint` file.
+//! Run the tests with `TAINT_BLESS=1` to write the current states to these files instead.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use rustc_middle::mir::{Body, Location};
+use rustc_middle::ty::TyCtxt;
+use rustc_mir::dataflow::Analysis;
+
+use crate::attributes::AttrInfo;
+use crate::test_utils::{find_fn, with_tcx, CoreConfig};
+
+use super::taint_analysis::TaintAnalysis;
+
+const FUNCTION: &str = "analyzed";
+
+/// The programs under `tests/golden`, in a stable order.
+fn programs() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut programs = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    programs.sort();
+    programs
+}
+
+/// The tainted locals before each statement and terminator of `body`, next to them.
+fn render<'tcx>(tcx: TyCtxt<'tcx>, body: &'tcx Body<'tcx>) -> String {
+    let info = AttrInfo::default();
+    let config = CoreConfig::default();
+    let init = (0..body.arg_count).map(|i| Some(i == 0)).collect();
+    let mut results = TaintAnalysis::new_with_init(tcx, &info, &config, body, Rc::default(), init)
+        .into_results()
+        .into_results_cursor(body);
+
+    let mut text = String::new();
+    for (block, data) in body.basic_blocks().iter_enumerated() {
+        let _ = writeln!(text, "{:?}:", block);
+        for (statement_index, statement) in data.statements.iter().enumerate() {
+            results.seek_before_primary_effect(Location {
+                block,
+                statement_index,
+            });
+            let _ = writeln!(text, "    {:?} {:?}", results.get().tainted, statement);
+        }
+
+        results.seek_before_primary_effect(body.terminator_loc(block));
+        let _ = writeln!(
+            text,
+            "    {:?} {:?}",
+            results.get().tainted,
+            data.terminator().kind
+        );
+    }
+    text
+}
+
+/// The lines which differ between `expected` and `actual`, by position.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    let mut text = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(i), actual.get(i));
+        if old != new {
+            let _ = writeln!(text, "line {}:", i + 1);
+            if let Some(old) = old {
+                let _ = writeln!(text, "-{}", old);
+            }
+            if let Some(new) = new {
+                let _ = writeln!(text, "+{}", new);
+            }
+        }
+    }
+    text
+}
+
+#[test]
+fn implements_analysis() {
+    fn check<'tcx, A: Analysis<'tcx>>() {}
+    check::<TaintAnalysis<'static, 'static>>();
+}
+
+#[test]
+fn golden_states() {
+    let bless = std::env::var_os("TAINT_BLESS").is_some();
+    let programs = programs();
+    assert!(programs.len() >= 3, "missing golden programs");
+
+    let mut failures = String::new();
+    for program in programs {
+        let source = std::fs::read_to_string(&program).unwrap();
+        let actual = with_tcx(&source, |tcx| {
+            render(tcx, tcx.optimized_mir(find_fn(tcx, FUNCTION)))
+        });
+
+        let golden = program.with_extension("taint");
+        if bless {
+            std::fs::write(&golden, &actual).unwrap();
+            continue;
+        }
+
+        let expected = std::fs::read_to_string(&golden).unwrap_or_default();
+        if expected != actual {
+            let _ = write!(
+                failures,
+                "{}:\n{}",
+                golden.display(),
+                diff(&expected, &actual)
+            );
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "tainted locals differ from the golden files, run with `TAINT_BLESS=1` if this is intended\n{}",
+        failures
+    );
+}
@@ -1,4 +1,25 @@
+pub mod analysis_config;
+pub mod attributes;
+pub mod convergence;
+#[cfg(feature = "eval")]
+pub mod expectations;
+#[cfg(feature = "eval")]
+pub mod query;
+#[cfg(feature = "eval")]
+pub mod report;
+#[cfg(feature = "eval")]
+pub mod reporter;
+#[cfg(feature = "eval")]
+pub mod rules;
+pub mod rvalue_model;
 pub mod taint_analysis;
+pub mod violation;
 
+mod control_dependence;
 pub(crate) mod errors;
 mod taint_domain;
+
+#[cfg(test)]
+mod golden;
+#[cfg(test)]
+pub(crate) mod test_utils;
@@ -0,0 +1,196 @@
+//! Answers to queries about the results of an analysis, as written with `--taint-results`.
+//!
+//! These are asked interactively through the `taint-query` binary, to triage the violations of large crates.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use super::report::CrateReport;
+use super::violation::SINK_RULE;
+
+/// The queries which `answer` understands.
+pub const HELP: &str = "\
+findings                             list the violations, numbered from 1
+trace <n>                            show where the tainted input of violation <n> came from
+sources <sink>                       list the sources whose values reach <sink>
+tainted <function> <local> <block>   whether <local> is tainted before a location, such as `tainted main _7 bb3[2]`
+quit                                 stop";
+
+/// The answer to `query` about `results`, or `None` if it asks to stop.
+pub fn answer(results: &CrateReport, query: &str) -> Option<String> {
+    let words = query.split_whitespace().collect::<Vec<_>>();
+    let answer = match words.as_slice() {
+        [] => String::new(),
+        ["quit"] | ["exit"] => return None,
+        ["help"] => HELP.to_owned(),
+        ["findings"] => findings(results),
+        ["trace", n] => match n.parse::<usize>() {
+            Ok(n) => trace(results, n),
+            Err(_) => format!("`{}` is not the number of a finding", n),
+        },
+        ["sources", sink] => sources(results, sink),
+        ["tainted", function, local, location] => tainted(results, function, local, location),
+        _ => format!("unknown query `{}`, try `help`", query.trim()),
+    };
+    Some(answer)
+}
+
+fn findings(results: &CrateReport) -> String {
+    if results.violations.is_empty() {
+        return "no violations".to_owned();
+    }
+
+    let mut text = String::new();
+    for (i, violation) in results.violations.iter().enumerate() {
+        // Other rules are not about a call, such as a bounds check on a tainted index.
+        if violation.rule == SINK_RULE {
+            let _ = writeln!(
+                text,
+                "{}: `{}` called from `{}` at {}",
+                i + 1,
+                violation.sink,
+                violation.function,
+                violation.location
+            );
+        } else {
+            let _ = writeln!(
+                text,
+                "{}: {} [{}] in `{}` at {}",
+                i + 1,
+                violation.message(),
+                violation.rule,
+                violation.function,
+                violation.location
+            );
+        }
+    }
+    text.pop();
+    text
+}
+
+fn trace(results: &CrateReport, n: usize) -> String {
+    let violation = match n
+        .checked_sub(1)
+        .and_then(|i| results.violations.iter().nth(i))
+    {
+        Some(violation) => violation,
+        None => return format!("there is no finding {}", n),
+    };
+
+    let source = match &violation.source {
+        Some(source) => format!("tainted at {}", source),
+        None => "tainted along an unknown path".to_owned(),
+    };
+    let reached = if violation.rule == SINK_RULE {
+        format!("passed to `{}`", violation.sink)
+    } else {
+        format!("{} [{}]", violation.message(), violation.rule)
+    };
+    format!(
+        "{}\n  -> {} in `{}` at {}\n  confidence: {}",
+        source,
+        reached,
+        violation.function,
+        violation.location,
+        violation.confidence.as_str()
+    )
+}
+
+fn sources(results: &CrateReport, sink: &str) -> String {
+    let sources = results
+        .violations
+        .iter()
+        .filter(|violation| violation.sink == sink)
+        .map(|violation| violation.source.as_deref().unwrap_or("an unknown source"))
+        .collect::<BTreeSet<_>>();
+
+    if sources.is_empty() {
+        format!("`{}` does not receive tainted input", sink)
+    } else {
+        sources.into_iter().collect::<Vec<_>>().join("\n")
+    }
+}
+
+fn tainted(results: &CrateReport, function: &str, local: &str, location: &str) -> String {
+    let states = match results.states.get(function) {
+        Some(states) => states,
+        None => return format!("`{}` was not analyzed", function),
+    };
+
+    // Locations without tainted locals are not recorded.
+    let tainted = states
+        .iter()
+        .find(|(at, _)| at == location)
+        .map_or(false, |(_, locals)| {
+            locals.iter().any(|tainted| tainted == local)
+        });
+    if tainted {
+        format!("{} is tainted before {} in `{}`", local, location, function)
+    } else {
+        format!(
+            "{} is not tainted before {} in `{}`",
+            local, location, function
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESULTS: &str = "\
+violation\tsrc/main.rs:9:5\toutput\thelper\tlow\t\tT0001
+violation\tsrc/main.rs:6:5\tindex\tmain\tmedium\tsrc/main.rs:3:17\tT0006
+violation\tsrc/main.rs:4:5\toutput\tmain\thigh\tsrc/main.rs:3:17\tT0001
+state\tmain\tbb1[0]\t_1
+state\tmain\tbb1[1]\t_1 _3
+";
+
+    #[test]
+    fn scripted_queries() {
+        let results = CrateReport::from_results(RESULTS);
+        let ask = |query| answer(&results, query).unwrap();
+
+        assert_eq!(
+            ask("findings"),
+            "1: `output` called from `helper` at src/main.rs:9:5\n\
+             2: tainted index used in bounds-checked access [T0006] in `main` at src/main.rs:6:5\n\
+             3: `output` called from `main` at src/main.rs:4:5"
+        );
+        assert_eq!(
+            ask("trace 2"),
+            "tainted at src/main.rs:3:17\n  -> tainted index used in bounds-checked access [T0006] in `main` at src/main.rs:6:5\n  confidence: medium"
+        );
+        assert_eq!(
+            ask("trace 3"),
+            "tainted at src/main.rs:3:17\n  -> passed to `output` in `main` at src/main.rs:4:5\n  confidence: high"
+        );
+        assert_eq!(ask("trace 4"), "there is no finding 4");
+        assert_eq!(ask("sources output"), "an unknown source\nsrc/main.rs:3:17");
+        assert_eq!(ask("sources log"), "`log` does not receive tainted input");
+        assert_eq!(
+            ask("tainted main _3 bb1[1]"),
+            "_3 is tainted before bb1[1] in `main`"
+        );
+        assert_eq!(
+            ask("tainted main _3 bb1[0]"),
+            "_3 is not tainted before bb1[0] in `main`"
+        );
+        assert_eq!(ask("tainted other _3 bb1[0]"), "`other` was not analyzed");
+        assert_eq!(answer(&results, "quit"), None);
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let results = CrateReport::from_results(RESULTS);
+        assert_eq!(results.to_results(), RESULTS);
+
+        // Results written before the rule was recorded are of sinks.
+        let older =
+            CrateReport::from_results("violation\tsrc/main.rs:9:5\toutput\thelper\tlow\t\n");
+        assert_eq!(
+            older.to_results(),
+            "violation\tsrc/main.rs:9:5\toutput\thelper\tlow\t\tT0001\n"
+        );
+    }
+}
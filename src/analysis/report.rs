@@ -0,0 +1,1103 @@
+//! Machine-readable results of the taint analysis, and comparisons between them.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::violation::{
+    Confidence, SinkStatus, SinkViolation, GUARD_RULE, INDEX_RULE, PANIC_MESSAGE_RULE, PANIC_RULE,
+    SINK_RULE,
+};
+
+/// The rules which violations are recorded for, with a description of each.
+pub const RULES: &[(&str, &str)] = &[
+    (SINK_RULE, "A sink received tainted input"),
+    (PANIC_RULE, "A tainted value may cause a panic"),
+    (GUARD_RULE, "A sink is called depending on tainted input"),
+    (
+        INDEX_RULE,
+        "A tainted index is used in a bounds-checked access",
+    ),
+    (
+        PANIC_MESSAGE_RULE,
+        "A tainted value is formatted into a panic message",
+    ),
+];
+
+impl SinkViolation {
+    fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"function\":{},\"sink\":{},\"rule\":{},\"location\":{},\"confidence\":{}",
+            json_string(&self.function),
+            json_string(&self.sink),
+            json_string(&self.rule),
+            json_string(&self.location),
+            json_string(self.confidence.as_str())
+        );
+        if !self.fingerprint.is_empty() {
+            let _ = write!(json, ",\"fingerprint\":{}", json_string(&self.fingerprint));
+        }
+        if let Some(source) = &self.source {
+            let _ = write!(json, ",\"source\":{}", json_string(source));
+        }
+        if !self.metadata.is_empty() {
+            let entries = self
+                .metadata
+                .iter()
+                .map(|(key, value)| format!("{}:{}", json_string(key), json_string(value)))
+                .collect::<Vec<_>>();
+            let _ = write!(json, ",\"metadata\":{{{}}}", entries.join(","));
+        }
+        json.push('}');
+        json
+    }
+
+    fn to_sarif(&self) -> String {
+        // Locations are `file:line:column`, and the file may contain colons itself.
+        let mut parts = self.location.rsplitn(3, ':');
+        let number = |part: Option<&str>| part.and_then(|part| part.parse::<u32>().ok());
+        let (column, line) = (number(parts.next()), number(parts.next()));
+        let (file, region) = match (parts.next(), line, column) {
+            (Some(file), Some(line), Some(column)) => (
+                file,
+                format!(
+                    ",\"region\":{{\"startLine\":{},\"startColumn\":{}}}",
+                    line, column
+                ),
+            ),
+            _ => (self.location.as_str(), String::new()),
+        };
+        let fingerprints = if self.fingerprint.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ",\"partialFingerprints\":{{\"taint/v1\":{}}}",
+                json_string(&self.fingerprint)
+            )
+        };
+        format!(
+            "{{\"ruleId\":{},\"level\":\"error\",\"message\":{{\"text\":{}}},\
+             \"locations\":[{{\"physicalLocation\":{{\"artifactLocation\":{{\"uri\":{}}}{}}}}}]{},\
+             \"properties\":{{\"function\":{},\"confidence\":{}}}}}",
+            json_string(&self.rule),
+            json_string(&self.message()),
+            json_string(file),
+            region,
+            fingerprints,
+            json_string(&self.function),
+            json_string(self.confidence.as_str())
+        )
+    }
+}
+
+/// Add `found` to `violations`, joining those which have the same `key` into one.
+pub(crate) fn merge_violations(
+    violations: &mut BTreeSet<SinkViolation>,
+    found: BTreeSet<SinkViolation>,
+) {
+    for violation in found {
+        let same = violations
+            .iter()
+            .find(|other| other.key() == violation.key())
+            .cloned();
+        let merged = match same {
+            Some(same) => {
+                violations.remove(&same);
+                same.join(violation)
+            }
+            None => violation,
+        };
+        violations.insert(merged);
+    }
+}
+
+/// A MIR construct which the analysis encountered, but does not handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnhandledConstruct {
+    /// How many locations it occurs at.
+    pub count: usize,
+    /// The function containing one of these locations.
+    pub function: String,
+    /// One of the locations, such as `bb1[2]`.
+    pub location: String,
+}
+
+impl UnhandledConstruct {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"count\":{},\"function\":{},\"location\":{}}}",
+            self.count,
+            json_string(&self.function),
+            json_string(&self.location)
+        )
+    }
+}
+
+/// How taint flows through a public function, for use by other crates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionSummary {
+    pub function: String,
+    /// `source`, `sink` or `sanitizer` if the function is annotated.
+    pub kind: Option<String>,
+    /// Whether the return value is tainted, even if no argument is.
+    pub returns_tainted: bool,
+    /// Indices of the arguments whose taint reaches the return value.
+    /// Empty if the return value is tainted regardless.
+    pub arg_to_return: Vec<usize>,
+    /// Indices of the arguments whose taint may cause a panic,
+    /// such as by unwrapping or indexing with a tainted value.
+    pub arg_to_panic: Vec<usize>,
+    /// Indices of the arguments whose taint reaches a sink.
+    pub arg_to_sink: Vec<usize>,
+}
+
+impl FunctionSummary {
+    pub fn to_json(&self) -> String {
+        let indices = |list: &[usize]| {
+            let items = list.iter().map(usize::to_string).collect::<Vec<_>>();
+            format!("[{}]", items.join(","))
+        };
+        format!(
+            "{{\"function\":{},\"kind\":{},\"returns_tainted\":{},\"arg_to_return\":{},\"arg_to_panic\":{},\"arg_to_sink\":{}}}",
+            json_string(&self.function),
+            self.kind.as_deref().map_or("null".to_owned(), json_string),
+            self.returns_tainted,
+            indices(&self.arg_to_return),
+            indices(&self.arg_to_panic),
+            indices(&self.arg_to_sink)
+        )
+    }
+}
+
+/// Summaries of the public functions of a crate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiSummary {
+    pub functions: Vec<FunctionSummary>,
+}
+
+impl ApiSummary {
+    pub fn to_json(&self) -> String {
+        let items = self
+            .functions
+            .iter()
+            .map(FunctionSummary::to_json)
+            .collect::<Vec<_>>();
+        format!("{{\"functions\":[{}]}}", items.join(","))
+    }
+
+    /// One row per function, listing the arguments which reach its return value, a panic or a sink.
+    pub fn to_table(&self) -> String {
+        let args = |list: &[usize]| {
+            let items = list
+                .iter()
+                .map(|arg| format!("arg{}", arg))
+                .collect::<Vec<_>>();
+            items.join(", ")
+        };
+        let mut rows = vec![[
+            "function".to_owned(),
+            "kind".to_owned(),
+            "return".to_owned(),
+            "panic".to_owned(),
+            "sink".to_owned(),
+        ]];
+        for function in self.functions.iter() {
+            rows.push([
+                function.function.clone(),
+                function.kind.clone().unwrap_or_default(),
+                if function.returns_tainted {
+                    "always".to_owned()
+                } else {
+                    args(&function.arg_to_return)
+                },
+                args(&function.arg_to_panic),
+                args(&function.arg_to_sink),
+            ]);
+        }
+
+        let mut widths = [0; 5];
+        for row in rows.iter() {
+            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let mut table = String::new();
+        for row in rows.iter() {
+            let cells = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>();
+            let _ = writeln!(table, "{}", cells.join(" | ").trim_end());
+        }
+        table
+    }
+}
+
+/// The locals which may be tainted in each block of each function, under the most conservative options.
+/// It is not a list of violations, but a way to check by hand that nothing dangerous is possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaxTaint {
+    /// The locals of each block, such as `bb3`, along with the names of the variables they hold, by function.
+    pub functions: BTreeMap<String, Vec<(String, Vec<String>)>>,
+}
+
+impl MaxTaint {
+    /// Each function, followed by one line per block listing its locals, such as `bb0: _1 (value), _3`.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (function, blocks) in self.functions.iter() {
+            let _ = writeln!(text, "{}:", function);
+            for (block, locals) in blocks.iter() {
+                let _ = write!(text, "    {}:", block);
+                if !locals.is_empty() {
+                    let _ = write!(text, " {}", locals.join(", "));
+                }
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+/// All violations found while analyzing a crate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrateReport {
+    pub violations: BTreeSet<SinkViolation>,
+    /// Unhandled constructs by name, such as `Rvalue::Len`.
+    /// Only collected with `--taint-report-unhandled`.
+    pub unhandled: BTreeMap<String, UnhandledConstruct>,
+    /// Summary of the public API, only computed with `--taint-export-summary` or `--taint-api-table`.
+    pub api: Option<ApiSummary>,
+    /// The status of every sink annotated in the crate, by path.
+    pub sinks: BTreeMap<String, SinkStatus>,
+    /// How many violations were not reported, because their confidence was below `--taint-min-confidence`.
+    pub suppressed: usize,
+    /// The tainted locals before each location of each analyzed body, such as `bb3[2]`, in order.
+    /// Only recorded with `--taint-results`, or for the function analyzed with `--taint-function`.
+    pub states: BTreeMap<String, Vec<(String, Vec<String>)>>,
+    /// The calls in every analyzed function, including the summarized ones.
+    pub calls: BTreeSet<CallEdge>,
+    /// Functions which were called beyond `--taint-max-call-depth`, and treated like functions without a body.
+    pub depth_limited: BTreeSet<String>,
+    /// The locals which may be tainted in each block, only computed with `--taint-max-taint`.
+    pub max_taint: Option<MaxTaint>,
+}
+
+impl CrateReport {
+    /// The functions which were analyzed or called, and the calls between them.
+    pub fn call_graph(&self) -> CallGraph {
+        let nodes = self
+            .calls
+            .iter()
+            .flat_map(|edge| vec![edge.caller.clone(), edge.callee.clone()])
+            .collect();
+        CallGraph {
+            nodes,
+            edges: self.calls.clone(),
+        }
+    }
+
+    /// Let `f` change or annotate each violation, before the report is rendered.
+    pub fn for_each_violation(&mut self, mut f: impl FnMut(&mut SinkViolation)) {
+        let violations = std::mem::take(&mut self.violations);
+        self.violations = violations
+            .into_iter()
+            .map(|mut violation| {
+                f(&mut violation);
+                violation
+            })
+            .collect();
+    }
+
+    /// Each violation along with the source around it, for reading without the compiler output.
+    /// The fingerprint of a violation follows it in brackets, if it is known.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for violation in self.violations.iter() {
+            let _ = write!(
+                text,
+                "{}: `{}` called from `{}` with tainted input",
+                violation.location, violation.sink, violation.function
+            );
+            if violation.rule != SINK_RULE {
+                let _ = write!(text, " ({})", violation.rule);
+            }
+            if !violation.fingerprint.is_empty() {
+                let _ = write!(text, " [{}]", violation.fingerprint);
+            }
+            let _ = writeln!(text, "\n{}", violation.snippet);
+        }
+        text
+    }
+
+    /// Read the violations back from the output of `to_text`, without their snippets and confidence.
+    pub fn from_text(text: &str) -> Self {
+        let violations = text
+            .lines()
+            .filter_map(|line| {
+                let (line, fingerprint) = match line
+                    .strip_suffix(']')
+                    .and_then(|line| line.rsplit_once(" ["))
+                {
+                    Some((line, fingerprint)) => (line, fingerprint),
+                    None => (line, ""),
+                };
+                let (line, rule) = match line
+                    .strip_suffix(')')
+                    .and_then(|line| line.rsplit_once(" ("))
+                {
+                    Some((line, rule)) => (line, rule),
+                    None => (line, SINK_RULE),
+                };
+                let line = line.strip_suffix("` with tainted input")?;
+                let (location, rest) = line.split_once(": `")?;
+                let (sink, function) = rest.split_once("` called from `")?;
+                Some(SinkViolation {
+                    function: function.to_owned(),
+                    sink: sink.to_owned(),
+                    rule: rule.to_owned(),
+                    location: location.to_owned(),
+                    fingerprint: fingerprint.to_owned(),
+                    snippet: String::new(),
+                    confidence: Confidence::Low,
+                    source: None,
+                    metadata: BTreeMap::new(),
+                })
+            })
+            .collect();
+
+        CrateReport {
+            violations,
+            ..CrateReport::default()
+        }
+    }
+
+    /// The violations and the tainted locals at every location, one per line, as read by `taint-query`.
+    /// Fields are separated by tabs, and the states of locations without tainted locals are left out.
+    /// The rule of a violation comes last, so that results written before it was recorded can still be read.
+    pub fn to_results(&self) -> String {
+        let mut text = String::new();
+        for violation in self.violations.iter() {
+            let _ = writeln!(
+                text,
+                "violation\t{}\t{}\t{}\t{}\t{}\t{}",
+                violation.location,
+                violation.sink,
+                violation.function,
+                violation.confidence.as_str(),
+                violation.source.as_deref().unwrap_or_default(),
+                violation.rule
+            );
+        }
+        for (function, states) in self.states.iter() {
+            for (location, locals) in states.iter().filter(|(_, locals)| !locals.is_empty()) {
+                let _ = writeln!(
+                    text,
+                    "state\t{}\t{}\t{}",
+                    function,
+                    location,
+                    locals.join(" ")
+                );
+            }
+        }
+        text
+    }
+
+    /// The tainted locals before every location of each function whose states were recorded,
+    /// such as the function analyzed with `--taint-function`, with a header for each function.
+    pub fn to_states(&self) -> String {
+        let mut text = String::new();
+        for (function, states) in self.states.iter() {
+            let _ = writeln!(text, "{}:", function);
+            for (location, locals) in states.iter() {
+                let _ = writeln!(text, "    {}: {}", location, locals.join(" "));
+            }
+        }
+        text
+    }
+
+    /// Read the output of `to_results` back, without the snippets of the violations.
+    /// Violations without a rule, as written by older versions, are of sinks which received tainted input.
+    pub fn from_results(text: &str) -> Self {
+        let mut report = CrateReport::default();
+        for line in text.lines() {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            match fields.as_slice() {
+                ["violation", location, sink, function, confidence, source, rule @ ..]
+                    if rule.len() <= 1 =>
+                {
+                    report.violations.insert(SinkViolation {
+                        function: (*function).to_owned(),
+                        sink: (*sink).to_owned(),
+                        rule: rule.first().copied().unwrap_or(SINK_RULE).to_owned(),
+                        location: (*location).to_owned(),
+                        fingerprint: String::new(),
+                        snippet: String::new(),
+                        confidence: Confidence::parse(confidence).unwrap_or_default(),
+                        source: Some((*source).to_owned()).filter(|source| !source.is_empty()),
+                        metadata: BTreeMap::new(),
+                    });
+                }
+                ["state", function, location, locals] => {
+                    let locals = locals.split(' ').map(str::to_owned).collect();
+                    report
+                        .states
+                        .entry((*function).to_owned())
+                        .or_default()
+                        .push(((*location).to_owned(), locals));
+                }
+                _ => {}
+            }
+        }
+        report
+    }
+
+    pub fn to_json(&self) -> String {
+        let unhandled = self
+            .unhandled
+            .iter()
+            .map(|(name, construct)| format!("{}:{}", json_string(name), construct.to_json()))
+            .collect::<Vec<_>>();
+        let mut json = format!(
+            "{{\"violations\":{},\"unhandled\":{{{}}}",
+            json_list(&self.violations),
+            unhandled.join(",")
+        );
+        if !self.sinks.is_empty() {
+            let sinks = self
+                .sinks
+                .iter()
+                .map(|(sink, status)| {
+                    format!("{}:{}", json_string(sink), json_string(status.as_str()))
+                })
+                .collect::<Vec<_>>();
+            let _ = write!(json, ",\"sinks\":{{{}}}", sinks.join(","));
+        }
+        if self.suppressed > 0 {
+            let _ = write!(json, ",\"suppressed\":{}", self.suppressed);
+        }
+        json.push('}');
+        json
+    }
+
+    /// The violations as a SARIF 2.1.0 log, for code scanning tools.
+    /// Every violation is a result of the rule of its diagnostic, which is listed by the tool, located at the call to the sink
+    /// or at what may panic.
+    pub fn to_sarif(&self) -> String {
+        let rules = RULES
+            .iter()
+            .filter(|(id, _)| self.violations.iter().any(|v| v.rule == *id))
+            .map(|(id, description)| {
+                format!(
+                    "{{\"id\":{},\"shortDescription\":{{\"text\":{}}}}}",
+                    json_string(id),
+                    json_string(description)
+                )
+            })
+            .collect::<Vec<_>>();
+        let results = self
+            .violations
+            .iter()
+            .map(SinkViolation::to_sarif)
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"version\":\"2.1.0\",\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\
+             \"runs\":[{{\"tool\":{{\"driver\":{{\"name\":\"taint\",\"rules\":[{}]}}}},\"results\":[{}]}}]}}",
+            rules.join(","),
+            results.join(",")
+        )
+    }
+}
+
+/// A call from one function to another, as seen by the analysis.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CallEdge {
+    pub caller: String,
+    pub callee: String,
+    /// Where the call is, as `file:line:column`.
+    pub location: String,
+}
+
+/// The calls between the functions of the analyzed crate and those they call, by path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    pub nodes: BTreeSet<String>,
+    pub edges: BTreeSet<CallEdge>,
+}
+
+impl CallGraph {
+    /// The functions which `caller` calls, without duplicates.
+    pub fn callees(&self, caller: &str) -> BTreeSet<&str> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.caller == caller)
+            .map(|edge| edge.callee.as_str())
+            .collect()
+    }
+
+    /// The graph in the Graphviz format, with an edge for every call site, labeled with its location.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph calls {\n");
+        for node in self.nodes.iter() {
+            let _ = writeln!(dot, "    {};", json_string(node));
+        }
+        for edge in self.edges.iter() {
+            let _ = writeln!(
+                dot,
+                "    {} -> {} [label={}];",
+                json_string(&edge.caller),
+                json_string(&edge.callee),
+                json_string(&edge.location)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The violations of a new report, compared to those of an older one.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReportDiff {
+    /// Violations which only occur in the new report.
+    pub added: Vec<SinkViolation>,
+    /// Violations which only occur in the old report.
+    pub removed: Vec<SinkViolation>,
+    /// Violations which occur in both reports.
+    pub unchanged: Vec<SinkViolation>,
+}
+
+impl ReportDiff {
+    /// One line per changed violation, prefixed by `+` if it was added and `-` if it was removed.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (prefix, violations) in [("+", &self.added), ("-", &self.removed)].iter() {
+            for violation in violations.iter() {
+                let _ = writeln!(
+                    text,
+                    "{} {}: `{}` called from `{}`",
+                    prefix, violation.location, violation.sink, violation.function
+                );
+            }
+        }
+        let _ = writeln!(
+            text,
+            "{} added, {} removed, {} unchanged",
+            self.added.len(),
+            self.removed.len(),
+            self.unchanged.len()
+        );
+        text
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"added\":{},\"removed\":{},\"unchanged\":{}}}",
+            json_list(&self.added),
+            json_list(&self.removed),
+            json_list(&self.unchanged)
+        )
+    }
+}
+
+/// Classify the violations of `new` by whether they also occur in `old`.
+/// Fingerprints only match fingerprints, so if a violation of either report has none, such as in an older report,
+/// the violations of both are matched by location.
+pub fn diff(old: CrateReport, new: CrateReport) -> ReportDiff {
+    let by_location = old
+        .violations
+        .iter()
+        .chain(new.violations.iter())
+        .any(|v| v.fingerprint.is_empty());
+    let key = |v: &SinkViolation| {
+        let id = if by_location {
+            v.location.clone()
+        } else {
+            v.fingerprint.clone()
+        };
+        (v.function.clone(), v.sink.clone(), id)
+    };
+    let old_keys = old.violations.iter().map(key).collect::<BTreeSet<_>>();
+    let new_keys = new.violations.iter().map(key).collect::<BTreeSet<_>>();
+
+    let mut result = ReportDiff::default();
+    for violation in new.violations.iter() {
+        if old_keys.contains(&key(violation)) {
+            result.unchanged.push(violation.clone());
+        } else {
+            result.added.push(violation.clone());
+        }
+    }
+    for violation in old.violations.iter() {
+        if !new_keys.contains(&key(violation)) {
+            result.removed.push(violation.clone());
+        }
+    }
+    result
+}
+
+fn json_list<'a>(violations: impl IntoIterator<Item = &'a SinkViolation>) -> String {
+    let items = violations
+        .into_iter()
+        .map(SinkViolation::to_json)
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(result, "\\u{:04x}", c as u32);
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::config::TaintConfig;
+    use crate::test_utils::{analyze, violations, INPUT_OUTPUT};
+
+    fn violation(sink: &str, line: u32) -> SinkViolation {
+        SinkViolation {
+            function: "main".to_owned(),
+            sink: sink.to_owned(),
+            rule: SINK_RULE.to_owned(),
+            location: format!("src/main.rs:{}:5", line),
+            fingerprint: String::new(),
+            snippet: String::new(),
+            confidence: Confidence::Low,
+            source: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    fn report(violations: &[SinkViolation]) -> CrateReport {
+        CrateReport {
+            violations: violations.iter().cloned().collect(),
+            ..CrateReport::default()
+        }
+    }
+
+    #[test]
+    fn diff_classifies_violations() {
+        let baseline = report(&[violation("output", 8), violation("log", 9)]);
+        let modified = report(&[violation("output", 8), violation("exec", 12)]);
+
+        let result = diff(baseline, modified);
+
+        assert_eq!(result.added, vec![violation("exec", 12)]);
+        assert_eq!(result.removed, vec![violation("log", 9)]);
+        assert_eq!(result.unchanged, vec![violation("output", 8)]);
+    }
+
+    #[test]
+    fn diff_without_fingerprints_on_one_side() {
+        let fingerprinted = |sink: &str, line: u32, fingerprint: &str| SinkViolation {
+            fingerprint: fingerprint.to_owned(),
+            ..violation(sink, line)
+        };
+        let baseline = report(&[violation("output", 8), violation("output", 9)]);
+        let modified = report(&[
+            fingerprinted("output", 8, "a"),
+            fingerprinted("output", 10, "b"),
+        ]);
+
+        let result = diff(baseline, modified);
+
+        assert_eq!(result.added, vec![fingerprinted("output", 10, "b")]);
+        assert_eq!(result.removed, vec![violation("output", 9)]);
+        assert_eq!(result.unchanged, vec![fingerprinted("output", 8, "a")]);
+    }
+
+    #[test]
+    fn diff_output() {
+        let result = diff(report(&[]), report(&[violation("output", 8)]));
+
+        assert_eq!(
+            result.to_text(),
+            "+ src/main.rs:8:5: `output` called from `main`\n1 added, 0 removed, 0 unchanged\n"
+        );
+        assert_eq!(
+            result.to_json(),
+            "{\"added\":[{\"function\":\"main\",\"sink\":\"output\",\"rule\":\"T0001\",\"location\":\"src/main.rs:8:5\",\"confidence\":\"low\"}],\"removed\":[],\"unchanged\":[]}"
+        );
+    }
+
+    #[test]
+    fn read_text_report() {
+        let mut original = report(&[violation("output", 8), violation("exec", 12)]);
+        original.for_each_violation(|v| v.snippet = "8 |     output(x);\n".to_owned());
+
+        assert_eq!(
+            CrateReport::from_text(&original.to_text()).violations,
+            report(&[violation("output", 8), violation("exec", 12)]).violations
+        );
+    }
+
+    #[test]
+    fn sarif_output() {
+        assert_eq!(
+            report(&[violation("exec", 12)]).to_sarif(),
+            "{\"version\":\"2.1.0\",\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\
+             \"runs\":[{\"tool\":{\"driver\":{\"name\":\"taint\",\"rules\":[{\"id\":\"T0001\",\
+             \"shortDescription\":{\"text\":\"A sink received tainted input\"}}]}},\"results\":[\
+             {\"ruleId\":\"T0001\",\"level\":\"error\",\"message\":{\"text\":\"function `exec` received tainted input\"},\
+             \"locations\":[{\"physicalLocation\":{\"artifactLocation\":{\"uri\":\"src/main.rs\"},\
+             \"region\":{\"startLine\":12,\"startColumn\":5}}}],\
+             \"properties\":{\"function\":\"main\",\"confidence\":\"low\"}}]}]}"
+        );
+    }
+
+    #[test]
+    fn violations_of_other_rules() {
+        let index = SinkViolation {
+            rule: INDEX_RULE.to_owned(),
+            ..violation("index", 7)
+        };
+        let original = report(&[index.clone(), violation("exec", 12)]);
+
+        assert_eq!(
+            CrateReport::from_text(&original.to_text()).violations,
+            original.violations
+        );
+        assert_eq!(
+            report(&[index]).to_sarif(),
+            "{\"version\":\"2.1.0\",\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\
+             \"runs\":[{\"tool\":{\"driver\":{\"name\":\"taint\",\"rules\":[{\"id\":\"T0006\",\
+             \"shortDescription\":{\"text\":\"A tainted index is used in a bounds-checked access\"}}]}},\"results\":[\
+             {\"ruleId\":\"T0006\",\"level\":\"error\",\"message\":{\"text\":\"tainted index used in bounds-checked access\"},\
+             \"locations\":[{\"physicalLocation\":{\"artifactLocation\":{\"uri\":\"src/main.rs\"},\
+             \"region\":{\"startLine\":7,\"startColumn\":5}}}],\
+             \"properties\":{\"function\":\"main\",\"confidence\":\"low\"}}]}]}"
+        );
+    }
+
+    #[test]
+    fn post_process_violations() {
+        let mut result = report(&[violation("exec", 12)]);
+        result.for_each_violation(|v| {
+            if v.sink == "exec" {
+                v.metadata.insert("cwe".to_owned(), "CWE-78".to_owned());
+            }
+        });
+
+        assert_eq!(
+            result.to_json(),
+            "{\"violations\":[{\"function\":\"main\",\"sink\":\"exec\",\"rule\":\"T0001\",\"location\":\"src/main.rs:12:5\",\"confidence\":\"low\",\"metadata\":{\"cwe\":\"CWE-78\"}}],\"unhandled\":{}}"
+        );
+    }
+
+    #[test]
+    fn source_reaches_sink() {
+        let found = violations(
+            &[
+                r#"
+fn main() {
+    let value = input();
+    output(value + 1);
+}
+"#,
+                INPUT_OUTPUT,
+            ]
+            .concat(),
+        );
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].function, "main");
+        assert_eq!(found[0].sink, "output");
+        assert_eq!(found[0].location, "<program.rs>:4:5");
+        assert_eq!(
+            found[0].snippet,
+            "3 |     let value = input();\n4 |     output(value + 1);\n  |            ^^^^^^^^^\n5 | }\n"
+        );
+    }
+
+    #[test]
+    fn confidence_follows_path_shape() {
+        let found = violations(
+            &[
+                r#"
+fn main() {
+    let value = input();
+    let copy = value;
+    output(copy);
+    output(value * 2);
+}
+"#,
+                INPUT_OUTPUT,
+            ]
+            .concat(),
+        );
+
+        let confidence = |location: &str| {
+            found
+                .iter()
+                .find(|v| v.location == location)
+                .map(|v| v.confidence)
+        };
+        assert_eq!(confidence("<program.rs>:5:5"), Some(Confidence::High));
+        assert_eq!(confidence("<program.rs>:6:5"), Some(Confidence::Medium));
+    }
+
+    #[test]
+    fn panics_are_violations_of_their_own_rules() {
+        let program = r#"
+fn main() {
+    let values = [1, 2, 3];
+    let index = input();
+    output(values[index]);
+    Some(index).unwrap();
+}
+
+#[taint::source]
+fn input() -> usize {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
+"#;
+        let config = TaintConfig {
+            tainted_index_is_sink: true,
+            panic_on_tainted_is_sink: true,
+            ..TaintConfig::default()
+        };
+        let old = analyze(program, config.clone());
+        let found = old
+            .violations
+            .iter()
+            .map(|v| (v.location.as_str(), v.sink.as_str(), v.rule.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("<program.rs>:5:12", "index", "T0006"),
+                (
+                    "<program.rs>:6:17",
+                    "std::option::Option::<usize>::unwrap",
+                    "T0004"
+                ),
+            ]
+        );
+
+        // They are matched by the baseline, even when a branch is added above them.
+        let baseline = std::env::temp_dir().join(format!("taint-panics-{}", std::process::id()));
+        std::fs::write(&baseline, old.to_text()).unwrap();
+        let changed = program.replace(
+            "    let index = input();\n",
+            "    let index = input();\n    if index > 2 {\n        return;\n    }\n",
+        );
+        let config = TaintConfig {
+            baseline: Some(baseline.to_str().unwrap().to_owned()),
+            ..config
+        };
+        let found = analyze(&changed, config).violations;
+        std::fs::remove_file(&baseline).unwrap();
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn fingerprints_ignore_line_numbers() {
+        let program = &[
+            r#"
+fn main() {
+    let value = input();
+    output(1);
+    output(value);
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        let old = analyze(program, TaintConfig::default());
+        let moved = analyze(
+            &program.replace("fn main() {", "\nfn main() {\n"),
+            TaintConfig::default(),
+        );
+
+        let (old, moved) = (
+            old.violations.iter().next().unwrap(),
+            moved.violations.iter().next().unwrap(),
+        );
+        assert_eq!(old.location, "<program.rs>:5:5");
+        assert_eq!(moved.location, "<program.rs>:7:5");
+        assert!(!old.fingerprint.is_empty());
+        assert_eq!(old.fingerprint, moved.fingerprint);
+        assert_eq!(old.key(), moved.key());
+
+        // The fingerprint depends on which call to the sink it is.
+        let other = analyze(
+            &program.replace(
+                "output(1);\n    output(value);",
+                "output(value);\n    output(1);",
+            ),
+            TaintConfig::default(),
+        );
+        assert_ne!(
+            other.violations.iter().next().unwrap().fingerprint,
+            old.fingerprint
+        );
+    }
+
+    #[test]
+    fn violations_have_matching_category() {
+        let found = violations(
+            r#"
+fn main() {
+    query(read_file());
+    query(read_request());
+}
+
+#[taint::source(filesystem)]
+fn read_file() -> i32 {
+    1
+}
+
+#[taint::source(sql)]
+fn read_request() -> i32 {
+    1
+}
+
+#[taint::sink(sql)]
+fn query(_: i32) {}
+"#,
+        );
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].location, "<program.rs>:4:5");
+        assert_eq!(found[0].metadata["category"], "sql");
+    }
+
+    #[test]
+    fn violations_of_several_categories_are_joined() {
+        let found = violations(
+            r#"
+fn main() {
+    write_file(read_anything());
+    write_file(read_file());
+}
+
+#[taint::source]
+fn read_anything() -> i32 {
+    1
+}
+
+#[taint::source(filesystem)]
+fn read_file() -> i32 {
+    1
+}
+
+#[taint::sink(filesystem, network)]
+fn write_file(_: i32) {}
+"#,
+        );
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].location, "<program.rs>:3:5");
+        assert_eq!(found[0].metadata["category"], "filesystem,network");
+        assert_eq!(found[1].location, "<program.rs>:4:5");
+        assert_eq!(found[1].metadata["category"], "filesystem");
+    }
+
+    #[test]
+    fn sink_statuses() {
+        let report = analyze(
+            r#"
+fn main() {
+    let value = input();
+    output(value);
+    log(1);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
+
+#[taint::sink]
+fn log(_: i32) {}
+
+#[taint::sink]
+fn unused(_: i32) {}
+"#,
+            TaintConfig::default(),
+        );
+
+        let statuses = report
+            .sinks
+            .iter()
+            .map(|(sink, status)| (sink.as_str(), *status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ("log", SinkStatus::Clean),
+                ("output", SinkStatus::Violated),
+                ("unused", SinkStatus::Unreached),
+            ]
+        );
+    }
+
+    #[test]
+    fn call_graph_edges() {
+        let report = analyze(
+            &[
+                r#"
+fn main() {
+    let value = input();
+    helper(value);
+}
+
+fn helper(value: i32) {
+    output(value);
+}
+"#,
+                INPUT_OUTPUT,
+            ]
+            .concat(),
+            TaintConfig::default(),
+        );
+        let graph = report.call_graph();
+
+        let edges = graph
+            .edges
+            .iter()
+            .map(|edge| {
+                (
+                    edge.caller.as_str(),
+                    edge.callee.as_str(),
+                    edge.location.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                ("helper", "output", "<program.rs>:8:5"),
+                ("main", "helper", "<program.rs>:4:5"),
+                ("main", "input", "<program.rs>:3:17"),
+            ]
+        );
+        assert_eq!(
+            graph.nodes.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["helper", "input", "main", "output"]
+        );
+        assert!(graph
+            .to_dot()
+            .contains("    \"main\" -> \"helper\" [label=\"<program.rs>:4:5\"];\n"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Extension point for the formats the results of the analysis are written in.
+
+use std::fmt::Debug;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::eval::config::{ReportFormat, TaintConfig};
+use crate::report::CrateReport;
+
+/// Writes the results of the analysis of a crate somewhere, in some format.
+///
+/// The driver runs the reporter selected with `--taint-report` and `--taint-report-format`,
+/// followed by those registered in `TaintConfig::reporters`.
+pub trait Reporter: Debug + Send + Sync {
+    fn report(&self, report: &CrateReport) -> io::Result<()>;
+}
+
+/// Writes each violation along with the source around it, as read by `--taint-baseline`.
+#[derive(Debug)]
+pub struct TextReporter {
+    pub path: PathBuf,
+}
+
+impl Reporter for TextReporter {
+    fn report(&self, report: &CrateReport) -> io::Result<()> {
+        write(&self.path, report.to_text())
+    }
+}
+
+/// Writes the violations, the unhandled constructs and the status of each sink as JSON.
+#[derive(Debug)]
+pub struct JsonReporter {
+    pub path: PathBuf,
+}
+
+impl Reporter for JsonReporter {
+    fn report(&self, report: &CrateReport) -> io::Result<()> {
+        write(&self.path, report.to_json())
+    }
+}
+
+/// Writes the violations as a SARIF log, for code scanning tools.
+#[derive(Debug)]
+pub struct SarifReporter {
+    pub path: PathBuf,
+}
+
+impl Reporter for SarifReporter {
+    fn report(&self, report: &CrateReport) -> io::Result<()> {
+        write(&self.path, report.to_sarif())
+    }
+}
+
+/// The reporters to run after analyzing a crate with `config`.
+pub fn reporters(config: &TaintConfig) -> Vec<Arc<dyn Reporter>> {
+    let selected = config.report.as_ref().map(|path| {
+        let path = PathBuf::from(path);
+        let reporter: Arc<dyn Reporter> = match config.report_format {
+            ReportFormat::Text => Arc::new(TextReporter { path }),
+            ReportFormat::Json => Arc::new(JsonReporter { path }),
+            ReportFormat::Sarif => Arc::new(SarifReporter { path }),
+        };
+        reporter
+    });
+    selected
+        .into_iter()
+        .chain(config.reporters.iter().cloned())
+        .collect()
+}
+
+/// Write `contents` to `path`, naming the path in the error if it fails.
+fn write(path: &Path, contents: String) -> io::Result<()> {
+    std::fs::write(path, contents)
+        .map_err(|err| io::Error::new(err.kind(), format!("`{}`: {}", path.display(), err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::violation::{Confidence, SinkViolation, SINK_RULE};
+
+    /// Collects the sinks of the violations, instead of writing them anywhere.
+    #[derive(Debug, Default)]
+    struct CollectingReporter {
+        sinks: Mutex<Vec<String>>,
+    }
+
+    impl Reporter for CollectingReporter {
+        fn report(&self, report: &CrateReport) -> io::Result<()> {
+            let mut sinks = self.sinks.lock().unwrap();
+            sinks.extend(report.violations.iter().map(|v| v.sink.clone()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn custom_reporter() {
+        let collecting = Arc::new(CollectingReporter::default());
+        let config = TaintConfig {
+            reporters: vec![collecting.clone()],
+            ..TaintConfig::default()
+        };
+        let violation = |sink: &str| SinkViolation {
+            function: "main".to_owned(),
+            sink: sink.to_owned(),
+            rule: SINK_RULE.to_owned(),
+            location: "src/main.rs:4:5".to_owned(),
+            fingerprint: String::new(),
+            snippet: String::new(),
+            confidence: Confidence::High,
+            source: None,
+            metadata: BTreeMap::new(),
+        };
+        let report = CrateReport {
+            violations: vec![violation("exec"), violation("output")]
+                .into_iter()
+                .collect(),
+            ..CrateReport::default()
+        };
+
+        let reporters = reporters(&config);
+        assert_eq!(reporters.len(), 1);
+        for reporter in reporters {
+            reporter.report(&report).unwrap();
+        }
+        assert_eq!(*collecting.sinks.lock().unwrap(), vec!["exec", "output"]);
+    }
+}
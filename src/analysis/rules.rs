@@ -0,0 +1,321 @@
+//! A description of how the analysis propagates taint through each construct it handles,
+//! for tools which render it into a reference, such as a table in the documentation.
+//!
+//! The rules are listed by hand next to the analysis, and must be updated along with it.
+
+use crate::taint_analysis::{
+    COPY_METHODS, FORMAT_FUNCTIONS, IDENTITY_METHODS, OUTPUT_SINKS, OUTPUT_WRITERS,
+    SLICE_INDEX_METHODS, UNWRAP_OR_METHODS,
+};
+
+/// The kind of construct a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    /// A MIR statement, other than an assignment, which is described by its rvalue.
+    Statement,
+    /// The rvalue of an assignment.
+    Rvalue,
+    /// A MIR terminator, such as a call.
+    Terminator,
+    /// A call to a function or method which is modeled, rather than analyzed.
+    Function,
+}
+
+impl RuleKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RuleKind::Statement => "statement",
+            RuleKind::Rvalue => "rvalue",
+            RuleKind::Terminator => "terminator",
+            RuleKind::Function => "function",
+        }
+    }
+}
+
+/// How taint flows through a construct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleDescription {
+    pub kind: RuleKind,
+    /// The construct, such as `Rvalue::BinaryOp` or the path of a function.
+    pub construct: &'static str,
+    /// How the taint of the result depends on the taint of the inputs.
+    pub behavior: &'static str,
+    /// The option which enables or changes the rule, if any.
+    pub option: Option<&'static str>,
+}
+
+impl RuleDescription {
+    fn new(kind: RuleKind, construct: &'static str, behavior: &'static str) -> Self {
+        RuleDescription {
+            kind,
+            construct,
+            behavior,
+            option: None,
+        }
+    }
+
+    fn with_option(mut self, option: &'static str) -> Self {
+        self.option = Some(option);
+        self
+    }
+}
+
+/// All built-in propagation rules, grouped by the kind of construct.
+pub fn rules() -> Vec<RuleDescription> {
+    use RuleKind::*;
+
+    let mut rules = vec![
+        RuleDescription::new(
+            Statement,
+            "StatementKind::Assign",
+            "the destination takes the taint of the rvalue; assigning to a field does not clean the rest of the local",
+        ),
+        RuleDescription::new(
+            Statement,
+            "StatementKind::SetDiscriminant",
+            "keeps the taint of the enum, which its fields were assigned before",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Use",
+            "a place propagates the taint of its local, whatever its projection, such as another field of a union",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Use",
+            "a constant is clean, unless it points into a tainted static",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Use",
+            "a constant assigned in a branch taken on tainted data is tainted",
+        )
+        .with_option("implicit-flows"),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Use",
+            "a value read through a raw pointer is tainted",
+        )
+        .with_option("unsafe-conservative"),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::BinaryOp",
+            "tainted if either operand is tainted",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::BinaryOp",
+            "comparisons are clean",
+        )
+        .with_option("clean-comparisons"),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::CheckedBinaryOp",
+            "tainted if either operand is tainted",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::UnaryOp",
+            "propagates the taint of the operand",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Ref",
+            "a reference carries the taint of its referent, and tainting it taints the referent",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Cast",
+            "numeric, enum and pointer casts propagate the taint of the operand",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Discriminant",
+            "propagates the taint of the enum",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Len",
+            "the length of a tainted array or slice is tainted",
+        )
+        .with_option("len"),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Aggregate",
+            "an array, struct or enum variant is tainted if any of its fields is; other aggregates are built field by field",
+        ),
+        RuleDescription::new(
+            Rvalue,
+            "Rvalue::Repeat, Rvalue::ThreadLocalRef, Rvalue::AddressOf, Rvalue::NullaryOp",
+            "left to the registered rvalue models",
+        ),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Call",
+            "a source taints its result, a sanitizer cleans it, and a sink reports tainted arguments",
+        ),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Call",
+            "a function with a body is summarized for the taint of its arguments, which gives the taint of its result and arguments",
+        ),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Call",
+            "arguments moved into a call are clean after it, while copied arguments keep their taint and references take the taint written through them",
+        ),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Call",
+            "a function without a body follows the unknown call policy",
+        )
+        .with_option("unknown-call-policy"),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Call",
+            "a call from outside to a function within a sanitizer boundary returns a clean value",
+        )
+        .with_option("sanitizer-boundary"),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::SwitchInt",
+            "constants assigned in the blocks which are control dependent on a switch on tainted data are tainted",
+        )
+        .with_option("implicit-flows"),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::SwitchInt",
+            "sinks called in the blocks which are control dependent on a switch on tainted data are reported",
+        )
+        .with_option("tainted-guard-is-sink"),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Assert",
+            "a bounds check with a tainted index is reported",
+        )
+        .with_option("tainted-index-is-sink"),
+        RuleDescription::new(
+            Terminator,
+            "TerminatorKind::Drop, TerminatorKind::Yield, TerminatorKind::InlineAsm",
+            "not handled",
+        ),
+    ];
+
+    rules.extend(
+        IDENTITY_METHODS.iter().map(|path| {
+            RuleDescription::new(Function, path, "propagates the taint of the receiver")
+        }),
+    );
+    rules.extend(COPY_METHODS.iter().map(|path| {
+        RuleDescription::new(
+            Function,
+            path,
+            "propagates the taint of the receiver when implemented by the standard library or derived, and is analyzed otherwise",
+        )
+    }));
+    rules.extend(SLICE_INDEX_METHODS.iter().map(|path| {
+        RuleDescription::new(Function, path, "propagates the taint of the indexed slice")
+    }));
+    rules.extend(UNWRAP_OR_METHODS.iter().map(|path| {
+        RuleDescription::new(
+            Function,
+            path,
+            "tainted if the receiver, the default value or the captures of the closure are tainted",
+        )
+    }));
+    rules.extend(FORMAT_FUNCTIONS.iter().map(|path| {
+        RuleDescription::new(
+            Function,
+            path,
+            "tainted if any argument, or the value a reference argument points to, is tainted",
+        )
+    }));
+    rules.extend(vec![
+        RuleDescription::new(
+            Function,
+            "std::ops::Try::into_result, std::ops::Try::from_error, std::ops::Try::from_ok",
+            "propagates the taint of the value, as desugared from `?`",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::mem::swap",
+            "exchanges the taint of the referents of both arguments",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::mem::replace, std::mem::take",
+            "returns the taint of the referent of the first argument, which takes the taint of the second or is cleaned",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::iter::Iterator, std::iter::IntoIterator",
+            "the result of a method is tainted if the iterator or the closure passed to it is tainted",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::future::Future::poll",
+            "awaiting the future of an async function or block is tainted if its body returns a tainted value, given the taint of the future",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::collections::HashMap, std::collections::BTreeMap, std::vec::Vec",
+            "inserting or pushing a tainted key or element taints the collection, and everything read from a tainted collection, or iterated over, is tainted",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::io::Write::write_fmt, std::fmt::Write::write_fmt",
+            "tainted arguments taint the writer",
+        ),
+        RuleDescription::new(
+            Function,
+            "std::sync::mpsc::Sender::send, std::sync::mpsc::SyncSender::send, std::sync::mpsc::Receiver::recv",
+            "values received from a channel which tainted values of the same type were sent through are tainted",
+        )
+        .with_option("channels"),
+        RuleDescription::new(
+            Function,
+            "std::option::Option::unwrap, std::option::Option::expect, std::result::Result::unwrap, std::result::Result::expect",
+            "unwrapping a tainted value is reported",
+        )
+        .with_option("panic-on-tainted-is-sink"),
+        RuleDescription::new(
+            Function,
+            "std::panicking::begin_panic, core::panicking::panic_fmt, std::panic::panic_any",
+            "a panic message with a tainted value is reported",
+        )
+        .with_option("panic-message-is-sink"),
+    ]);
+    rules.extend(OUTPUT_SINKS.iter().chain(OUTPUT_WRITERS).map(|path| {
+        RuleDescription::new(Function, path, "tainted output is reported")
+            .with_option("output-sinks")
+    }));
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_constructs_are_described() {
+        let rules = rules();
+        let described = |construct: &str| rules.iter().any(|rule| rule.construct == construct);
+
+        for construct in [
+            "StatementKind::Assign",
+            "Rvalue::Use",
+            "Rvalue::BinaryOp",
+            "Rvalue::Ref",
+            "Rvalue::Aggregate",
+            "TerminatorKind::Call",
+            "TerminatorKind::SwitchInt",
+            "std::clone::Clone::clone",
+            "std::io::_print",
+        ]
+        .iter()
+        {
+            assert!(described(construct), "no rule for `{}`", construct);
+        }
+        assert!(rules.iter().all(|rule| !rule.behavior.is_empty()));
+    }
+}
@@ -0,0 +1,83 @@
+//! Extension point for the effect of rvalues which the analysis does not handle itself.
+
+use std::fmt::Debug;
+
+use rustc_middle::mir::{Place, Rvalue};
+
+use crate::taint_analysis::TransferFunction;
+
+/// Models how taint flows through an assignment the transfer function ignores,
+/// such as `Rvalue::Repeat` or `Rvalue::Cast`.
+///
+/// Models are returned by `AnalysisConfig::rvalue_models`, such as those registered in `TaintConfig::rvalue_models`,
+/// and tried in order.
+pub trait RvalueModel: Debug + Send + Sync {
+    /// Update the taint of `place`, which is assigned `rvalue`.
+    /// Returns whether the rvalue was handled, so that later models are skipped.
+    fn handle(
+        &self,
+        place: &Place<'_>,
+        rvalue: &Rvalue<'_>,
+        transfer: &mut TransferFunction,
+    ) -> bool;
+}
+
+#[cfg(all(test, feature = "eval"))]
+mod tests {
+    use std::sync::Arc;
+
+    use rustc_middle::mir::Operand;
+
+    use super::*;
+    use crate::eval::config::TaintConfig;
+    use crate::test_utils::analyze;
+
+    /// Taints an array if its repeated element is tainted.
+    #[derive(Debug)]
+    struct RepeatModel;
+
+    impl RvalueModel for RepeatModel {
+        fn handle(
+            &self,
+            place: &Place<'_>,
+            rvalue: &Rvalue<'_>,
+            transfer: &mut TransferFunction,
+        ) -> bool {
+            if let Rvalue::Repeat(Operand::Copy(element) | Operand::Move(element), _) = rvalue {
+                let tainted = transfer.is_tainted(element.local);
+                transfer.set_taint(place.local, tainted);
+                true
+            } else {
+                false
+            }
+        }
+    }
+
+    const PROGRAM: &str = r#"
+fn main() {
+    let buffer = [input(); 4];
+    output(buffer);
+}
+
+#[taint::source]
+fn input() -> u8 {
+    15
+}
+
+#[taint::sink]
+fn output(_: [u8; 4]) {}
+"#;
+
+    #[test]
+    fn custom_model() {
+        let without_model = analyze(PROGRAM, TaintConfig::default());
+        assert!(without_model.violations.is_empty());
+
+        let config = TaintConfig {
+            rvalue_models: vec![Arc::new(RepeatModel)],
+            ..TaintConfig::default()
+        };
+        let with_model = analyze(PROGRAM, config);
+        assert_eq!(with_model.violations.len(), 1);
+    }
+}
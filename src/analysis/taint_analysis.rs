@@ -1,96 +1,428 @@
 use std::{
     cell::RefCell,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::Hash,
     rc::Rc,
 };
 
+use rustc_data_structures::{fingerprint::Fingerprint, stable_hasher::StableHasher};
 use rustc_hir::def_id::DefId;
 use rustc_index::bit_set::BitSet;
 use rustc_middle::{
     mir::{
-        traversal::reverse_postorder, visit::Visitor, BasicBlock, Body, Constant, HasLocalDecls,
-        Local, Location, Operand, Place, Rvalue, Statement, StatementKind, Terminator,
-        TerminatorKind,
+        interpret::{ConstValue, GlobalAlloc, Scalar},
+        traversal::reverse_postorder,
+        visit::Visitor,
+        AggregateKind, AssertKind, BasicBlock, BasicBlockData, BinOp, Body, CastKind, Constant,
+        ConstantKind, HasLocalDecls, Local, Location, Operand, Place, ProjectionElem, Rvalue,
+        Statement, StatementKind, Terminator, TerminatorKind, RETURN_PLACE, START_BLOCK,
+    },
+    ty::{
+        subst::{GenericArgKind, SubstsRef},
+        ConstKind, Instance, ParamEnv, Ty, TyCtxt, TyKind,
     },
-    ty::{TyCtxt, TyKind},
 };
 
-use rustc_mir::dataflow::{Analysis, AnalysisDomain, Forward};
-use rustc_span::Span;
-
-use tracing::instrument;
+use rustc_mir::dataflow::{Analysis, AnalysisDomain, Forward, Results, ResultsRefCursor};
+use rustc_session::SessionDiagnostic;
+use rustc_span::{sym, Span};
 
-use crate::eval::attributes::{AttrInfo, AttrInfoKind};
+use tracing::{debug, debug_span, instrument};
 
-use super::taint_domain::{PointsAwareTaintDomain, TaintDomain};
+use super::{
+    analysis_config::{
+        AnalysisConfig, AnalysisOption, FlowSpec, FlowTarget, PathList, RecursionAssumption,
+        SourceSpec, UnknownCallPolicy,
+    },
+    attributes::{AttrInfo, AttrInfoKind, CategoryFilter},
+    control_dependence::{control_dependent, post_dominators},
+    convergence::ConvergenceTrace,
+    taint_domain::{Origins, PointsAwareTaintDomain, TaintDomain, TaintState},
+    violation::{
+        Confidence, SinkStatus, SinkViolation, GUARD_RULE, INDEX_RULE, PANIC_MESSAGE_RULE,
+        PANIC_RULE, SINK_RULE,
+    },
+};
 
 pub(crate) type PointsMap = HashMap<Local, HashSet<Local>>;
 pub(crate) type Contexts = HashMap<(DefId, InitSet), Option<BitSet<Local>>>;
 
-type InitSet = Vec<Option<bool>>;
+pub(crate) type InitSet = Vec<Option<bool>>;
+
+/// Methods which return their receiver, or a reference to it, as far as taint is concerned.
+/// Other such functions can be configured with `--taint-flow=<path>(arg0 -> ret)`.
+pub(crate) const IDENTITY_METHODS: &[&str] = &[
+    "std::convert::AsRef::as_ref",
+    "std::convert::AsMut::as_mut",
+    "std::borrow::Borrow::borrow",
+    "std::borrow::BorrowMut::borrow_mut",
+    "std::option::Option::<T>::as_ref",
+    "std::option::Option::<T>::as_mut",
+    "std::result::Result::<T, E>::as_ref",
+    "std::result::Result::<T, E>::as_mut",
+    // `vec!` builds a boxed slice, and converts it into a `Vec`.
+    "std::slice::<impl [T]>::into_vec",
+    "std::slice::<impl [T]>::to_vec",
+    // Conversions between strings and their bytes, which go through `transmute` and raw pointers.
+    "core::str::<impl str>::as_bytes",
+    "std::string::String::as_bytes",
+    "std::string::String::into_bytes",
+    "std::string::String::from_utf8",
+    "std::str::from_utf8",
+    // Smart pointers own the value they are built from, which goes through raw pointers in `Rc` and `Arc`.
+    // Dereferencing them goes through `Deref::deref`, or a projection for `Box`.
+    "std::boxed::Box::<T>::new",
+    "std::rc::Rc::<T>::new",
+    "std::sync::Arc::<T>::new",
+    // User types with their own operators are dereferenced and indexed through calls rather than projections.
+    "std::ops::Deref::deref",
+    "std::ops::DerefMut::deref_mut",
+    "std::ops::Index::index",
+    "std::ops::IndexMut::index_mut",
+];
+
+/// Methods which return a copy of their receiver, as far as taint is concerned,
+/// when the standard library implements them or they are derived.
+/// Other implementations are analyzed, since they may build the copy from anything.
+pub(crate) const COPY_METHODS: &[&str] = &[
+    "std::clone::Clone::clone",
+    "std::borrow::ToOwned::to_owned",
+    "std::string::ToString::to_string",
+];
+
+/// Methods through which strings and slices are indexed by ranges, such as by `&text[1..4]` or `text.get(1..4)`,
+/// which return a part of the slice they take after the index.
+pub(crate) const SLICE_INDEX_METHODS: &[&str] = &[
+    "std::slice::SliceIndex::get",
+    "std::slice::SliceIndex::get_mut",
+    "std::slice::SliceIndex::index",
+    "std::slice::SliceIndex::index_mut",
+];
+
+/// Methods which return the value of an `Option` or `Result`, or else a default value or the result of a closure,
+/// whose result is tainted if the receiver, the default value or the captures of the closure are.
+pub(crate) const UNWRAP_OR_METHODS: &[&str] = &[
+    "std::option::Option::<T>::unwrap_or",
+    "std::option::Option::<T>::unwrap_or_else",
+    "std::option::Option::<T>::unwrap_or_default",
+    "std::result::Result::<T, E>::unwrap_or",
+    "std::result::Result::<T, E>::unwrap_or_else",
+    "std::result::Result::<T, E>::unwrap_or_default",
+];
+
+/// Functions which `format_args!` and `format!` expand to, whose result is tainted if any argument is.
+pub(crate) const FORMAT_FUNCTIONS: &[&str] = &[
+    "std::fmt::ArgumentV1::<'a>::new",
+    "std::fmt::Arguments::<'a>::new_v1",
+    "std::fmt::Arguments::<'a>::new_v1_formatted",
+    "std::fmt::format",
+];
+
+/// Writers which `write!` to is an output sink, along with those configured with `--taint-sink-writer`.
+pub(crate) const OUTPUT_WRITERS: &[&str] = &[
+    "std::io::Stdout",
+    "std::io::StdoutLock",
+    "std::io::Stderr",
+    "std::io::StderrLock",
+];
+
+/// Functions which the printing and logging macros pass their formatted `Arguments` or values to,
+/// which are sinks with `output_sinks`.
+pub(crate) const OUTPUT_SINKS: &[&str] = &[
+    "std::io::_print",
+    "std::io::_eprint",
+    "log::__private_api_log",
+    "tracing_core::Event::dispatch",
+];
+
+/// State which is shared between the analyses of all bodies.
+#[derive(Default)]
+pub(crate) struct Shared<'tcx> {
+    /// Function summaries, computed once per combination of tainted arguments.
+    pub(crate) contexts: Contexts,
+    /// Closures and generators, and whether they may run with tainted captures, and with tainted arguments.
+    pub(crate) captures: HashMap<DefId, (bool, bool)>,
+    /// Sinks which may receive tainted input.
+    pub(crate) violations: BTreeSet<SinkViolation>,
+    /// Unhandled MIR constructs, and the functions and locations where they occur.
+    pub(crate) unhandled: BTreeMap<&'static str, BTreeSet<(String, String)>>,
+    /// Element types of channels which a tainted value was sent through.
+    pub(crate) tainted_channels: HashSet<Ty<'tcx>>,
+    /// How many panics a tainted value may cause, with `panic_on_tainted_is_sink`
+    /// and `tainted_index_is_sink`.
+    pub(crate) tainted_panics: usize,
+    /// Mutable statics which a tainted value was written to.
+    pub(crate) tainted_statics: HashSet<DefId>,
+    /// Keys of the violations in the baseline report, which are not reported again.
+    pub(crate) baseline: BTreeSet<(String, String, String)>,
+    /// Keys of the violations below `min_confidence`, which are counted but not reported.
+    pub(crate) suppressed: BTreeSet<(String, String, String)>,
+    /// Record violations without emitting diagnostics, such as while summarizing functions.
+    pub(crate) silent: bool,
+    /// The sources and sinks matched in the current round, if they have categories.
+    pub(crate) categories: CategoryFilter,
+    /// Annotated sinks which are called, and whether they may receive tainted input.
+    pub(crate) sinks: HashMap<DefId, SinkStatus>,
+    /// For each function summary, the arguments which the function calls, such as the closure
+    /// passed to `Option::map`, and whether it passes them tainted arguments.
+    pub(crate) callbacks: HashMap<(DefId, InitSet), BTreeSet<(usize, bool)>>,
+    /// The tainted locals before each statement and terminator of each analyzed body,
+    /// joined over all the contexts it was analyzed in. Only recorded with `results`.
+    pub(crate) states: HashMap<DefId, HashMap<Location, BitSet<Local>>>,
+    /// How many summaries are being computed, one within the other.
+    pub(crate) depth: usize,
+    /// Functions which were treated like functions without a body, because they were called beyond `max_call_depth`.
+    pub(crate) depth_limited: BTreeSet<String>,
+    /// The calls in every analyzed body, by caller, callee and the span of the call.
+    /// Calls through a trait are to the method of the trait, which is what their summary is computed for.
+    pub(crate) calls: HashSet<(DefId, DefId, Span)>,
+}
 
 /// A dataflow analysis that tracks whether a value may carry a taint.
 ///
 /// Taints are introduced through sources, and consumed by sinks.
 /// Ideally, a sink never consumes a tainted value - this should result in an error.
+///
+/// This is a may-analysis: where control flow merges, the states of the predecessors are joined
+/// by union, so a local is tainted after the merge if it is tainted on any incoming path.
 pub struct TaintAnalysis<'tcx, 'inter> {
     /// We use the type context to emit errors and get the MIR for other functions.
     tcx: TyCtxt<'tcx>,
     /// All the functions that have been marked
     info: &'inter AttrInfo,
-    contexts: Rc<RefCell<Contexts>>,
+    /// Which additional checks to perform
+    config: &'inter dyn AnalysisConfig,
+    /// The body being analyzed
+    body: &'tcx Body<'tcx>,
+    shared: Rc<RefCell<Shared<'tcx>>>,
     init: InitSet,
     points: RefCell<PointsMap>,
+    /// Blocks which only run depending on which target a `SwitchInt` whose discriminant may be tainted
+    /// branches to, as of the previous iteration to a fixpoint.
+    tainted_branches: HashSet<BasicBlock>,
+    /// Blocks which assign the boolean result of a `SwitchInt` on a tainted comparison or discriminant,
+    /// such as the arms of `matches!`, and the local they assign it to,
+    /// as of the previous iteration to a fixpoint.
+    tainted_tests: HashMap<BasicBlock, Local>,
+    /// Locals which hold the address of a static.
+    statics: RefCell<HashMap<Local, DefId>>,
+    /// Blocks which may be reached, given the branches which switch on a constant.
+    reachable: BitSet<BasicBlock>,
+    /// Sizes of the tainted sets during the fixpoint iteration, if they are recorded.
+    convergence: Option<RefCell<ConvergenceTrace>>,
 }
 
 impl<'tcx, 'inter> TaintAnalysis<'tcx, 'inter> {
     /// Call on `main` function
-    pub fn new(tcx: TyCtxt<'tcx>, info: &'inter AttrInfo) -> Self {
-        Self::new_with_init(
-            tcx,
-            info,
-            Rc::new(RefCell::new(Contexts::new())),
-            InitSet::new(),
-        )
+    pub fn new(
+        tcx: TyCtxt<'tcx>,
+        info: &'inter AttrInfo,
+        config: &'inter dyn AnalysisConfig,
+        body: &'tcx Body<'tcx>,
+    ) -> Self {
+        Self::new_with_init(tcx, info, config, body, Rc::default(), InitSet::new())
     }
 
     /// Call on dependencies
     #[inline]
-    fn new_with_init(
+    pub(crate) fn new_with_init(
         tcx: TyCtxt<'tcx>,
         info: &'inter AttrInfo,
-        contexts: Rc<RefCell<Contexts>>,
+        config: &'inter dyn AnalysisConfig,
+        body: &'tcx Body<'tcx>,
+        shared: Rc<RefCell<Shared<'tcx>>>,
         init: InitSet,
     ) -> Self {
         TaintAnalysis {
             tcx,
             info,
-            contexts,
+            config,
+            body,
+            shared,
             init,
             points: RefCell::new(PointsMap::new()),
+            tainted_branches: HashSet::new(),
+            tainted_tests: HashMap::new(),
+            statics: RefCell::new(HashMap::new()),
+            reachable: reachable_blocks(tcx, body),
+            convergence: None,
+        }
+    }
+
+    /// Whether the analysis should stop, because a violation was found with `fail_fast`.
+    pub(crate) fn stopped(&self) -> bool {
+        self.config.enabled(AnalysisOption::FailFast) && !self.shared.borrow().violations.is_empty()
+    }
+
+    /// Record how the tainted set of each block grows during the fixpoint iteration.
+    pub fn trace_convergence(mut self) -> Self {
+        self.convergence = Some(RefCell::default());
+        self
+    }
+
+    /// Run the analysis, and return the join of the states at every `Return` terminator,
+    /// or `None` if the body never returns.
+    pub fn into_exit_state(self) -> Option<BitSet<Local>> {
+        self.into_exit_state_and_trace().0
+    }
+
+    /// Like `into_exit_state`, but also return the convergence trace, if it is recorded.
+    pub fn into_exit_state_and_trace(self) -> (Option<BitSet<Local>>, Option<ConvergenceTrace>) {
+        let tcx = self.tcx;
+        let body = self.body;
+        let recorded = self
+            .config
+            .records_states(&tcx.def_path_str(body.source.def_id()));
+        let shared = recorded.then(|| self.shared.clone());
+        let mut results = self.into_results();
+
+        // Take the trace before the cursor applies the effects again.
+        let trace = results.analysis.convergence.take().map(RefCell::into_inner);
+        if let Some(trace) = &trace {
+            debug!("convergence of `{:?}`:\n{}", body.source.def_id(), trace);
+        }
+
+        let mut results = results.into_results_cursor(body);
+
+        if let Some(shared) = shared {
+            for (block, data) in body.basic_blocks().iter_enumerated() {
+                for statement_index in 0..=data.statements.len() {
+                    let location = Location {
+                        block,
+                        statement_index,
+                    };
+                    results.seek_before_primary_effect(location);
+                    let state = results.get().tainted.clone();
+
+                    let mut shared = shared.borrow_mut();
+                    let states = shared.states.entry(body.source.def_id()).or_default();
+                    match states.get_mut(&location) {
+                        Some(joined) => {
+                            joined.union(&state);
+                        }
+                        None => {
+                            states.insert(location, state);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut exit_state: Option<BitSet<Local>> = None;
+        for (block, data) in reverse_postorder(body) {
+            if !matches!(data.terminator().kind, TerminatorKind::Return) {
+                continue;
+            }
+
+            results.seek_to_block_end(block);
+            match exit_state.as_mut() {
+                Some(state) => {
+                    state.union(&results.get().tainted);
+                }
+                None => exit_state = Some(results.get().tainted.clone()),
+            }
+        }
+        (exit_state, trace)
+    }
+
+    /// Iterate to a fixpoint, and again for as long as more branches turn out to be taken on tainted data.
+    /// Which are depends on the taint of their discriminants, so it is only known once the iteration
+    /// has converged, and the effect of a branch on the blocks it leads to must be found by iterating again.
+    pub fn into_results(mut self) -> Results<'tcx, Self> {
+        let (tcx, body) = (self.tcx, self.body);
+        loop {
+            let results = self
+                .into_engine(tcx, body)
+                .pass_name("taint_analysis")
+                .iterate_to_fixpoint();
+
+            let (branches, tests) = Self::tainted_switches(&results);
+            let analysis = &results.analysis;
+            if branches == analysis.tainted_branches && tests == analysis.tainted_tests {
+                return results;
+            }
+
+            self = results.analysis;
+            self.tainted_branches = branches;
+            self.tainted_tests = tests;
+            // The iteration starts over from the bottom, which the trace must not take for shrinking sets.
+            if let Some(convergence) = &self.convergence {
+                convergence.replace(ConvergenceTrace::default());
+            }
+        }
+    }
+
+    /// The blocks which are control dependent on the `SwitchInt` terminators whose discriminant may be tainted
+    /// in `results`, and the blocks which assign the result of those which test a tainted comparison or discriminant.
+    fn tainted_switches(
+        results: &Results<'tcx, Self>,
+    ) -> (HashSet<BasicBlock>, HashMap<BasicBlock, Local>) {
+        let analysis = &results.analysis;
+        let body = analysis.body;
+        let mut cursor = ResultsRefCursor::new(body, results);
+        let mut dominators = None;
+
+        let (mut branches, mut tests) = (HashSet::new(), HashMap::new());
+        for (block, data) in body.basic_blocks().iter_enumerated() {
+            let (discr, targets) = match &data.terminator().kind {
+                TerminatorKind::SwitchInt { discr, targets, .. } => (discr, targets.all_targets()),
+                _ => continue,
+            };
+            let place = match discr.place() {
+                Some(place) if analysis.reachable.contains(block) => place,
+                _ => continue,
+            };
+
+            cursor.seek_before_primary_effect(body.terminator_loc(block));
+            if !cursor.get().tainted.get_taint(place.local) {
+                continue;
+            }
+
+            if !analysis.config.enabled(AnalysisOption::CleanComparisons)
+                && is_test(data, place.local)
+            {
+                tests.extend(test_results(body, targets));
+            }
+            if analysis.config.enabled(AnalysisOption::ImplicitFlows)
+                || analysis.config.enabled(AnalysisOption::TaintedGuardIsSink)
+            {
+                let dominators = dominators.get_or_insert_with(|| post_dominators(body));
+                branches.extend(control_dependent(body, block, dominators));
+            }
         }
+        (branches, tests)
     }
 }
 
-struct TransferFunction<'tcx, 'inter, 'intra> {
+/// Applies the effect of statements and terminators on the taint of locals.
+pub struct TransferFunction<'tcx, 'inter, 'intra> {
     tcx: TyCtxt<'tcx>,
     info: &'inter AttrInfo,
-    contexts: Rc<RefCell<Contexts>>,
+    config: &'inter dyn AnalysisConfig,
+    body: &'tcx Body<'tcx>,
+    shared: Rc<RefCell<Shared<'tcx>>>,
     state: &'intra mut PointsAwareTaintDomain<'intra, Local>,
+    location: Location,
+    tainted_branches: &'intra HashSet<BasicBlock>,
+    tainted_tests: &'intra HashMap<BasicBlock, Local>,
+    /// Where the taint of each local was introduced, for diagnostics, and how directly it flowed from there.
+    origins: &'intra mut Origins,
+    statics: &'intra RefCell<HashMap<Local, DefId>>,
+    init: &'intra InitSet,
 }
 
-impl<'inter> AnalysisDomain<'inter> for TaintAnalysis<'_, '_> {
-    type Domain = BitSet<Local>;
+impl<'tcx> AnalysisDomain<'tcx> for TaintAnalysis<'tcx, '_> {
+    type Domain = TaintState;
     const NAME: &'static str = "TaintAnalysis";
 
     type Direction = Forward;
 
-    fn bottom_value(&self, body: &Body<'inter>) -> Self::Domain {
+    fn bottom_value(&self, body: &Body<'tcx>) -> Self::Domain {
         // bottom = definitely untainted
-        BitSet::new_empty(body.local_decls().len())
+        TaintState::new_empty(body.local_decls().len())
     }
 
-    fn initialize_start_block(&self, body: &Body<'inter>, state: &mut Self::Domain) {
+    fn initialize_start_block(&self, body: &Body<'tcx>, state: &mut Self::Domain) {
         // For the main function, locals all start out untainted.
         // For other functions, however, we must check if they receive tainted parameters.
         if !self.init.is_empty() {
@@ -100,27 +432,46 @@ impl<'inter> AnalysisDomain<'inter> for TaintAnalysis<'_, '_> {
                 .zip(body.args_iter())
                 .filter(|(&t, _)| t.unwrap_or(false))
             {
-                state.set_taint(arg, true);
+                state.tainted.set_taint(arg, true);
+
+                // The taint comes from the caller, so point at the parameter.
+                // How it flowed in the caller is unknown.
+                let span = body.local_decls[arg].source_info.span;
+                state
+                    .origins
+                    .insert(arg, std::iter::once((Confidence::Medium, span)).collect());
             }
         }
     }
 }
 
-impl<'tcx, 'inter, 'intra> Analysis<'intra> for TaintAnalysis<'tcx, 'inter> {
+impl<'tcx> Analysis<'tcx> for TaintAnalysis<'tcx, '_> {
     fn apply_statement_effect(
         &self,
         state: &mut Self::Domain,
-        statement: &Statement<'intra>,
+        statement: &Statement<'tcx>,
         location: Location,
     ) {
+        if !self.reachable.contains(location.block) || self.stopped() {
+            return;
+        }
+
         TransferFunction {
             tcx: self.tcx,
             info: self.info,
-            contexts: self.contexts.clone(),
+            config: self.config,
+            body: self.body,
+            shared: self.shared.clone(),
             state: &mut PointsAwareTaintDomain {
-                state,
+                state: &mut state.tainted,
                 map: &mut self.points.borrow_mut(),
             },
+            location,
+            tainted_branches: &self.tainted_branches,
+            tainted_tests: &self.tainted_tests,
+            origins: &mut state.origins,
+            statics: &self.statics,
+            init: &self.init,
         }
         .visit_statement(statement, location);
     }
@@ -128,28 +479,51 @@ impl<'tcx, 'inter, 'intra> Analysis<'intra> for TaintAnalysis<'tcx, 'inter> {
     fn apply_terminator_effect(
         &self,
         state: &mut Self::Domain,
-        terminator: &Terminator<'intra>,
+        terminator: &Terminator<'tcx>,
         location: Location,
     ) {
+        // Blocks which are never reached must not contribute any taint where they merge with others.
+        if !self.reachable.contains(location.block) {
+            state.clear();
+            return;
+        }
+        if self.stopped() {
+            return;
+        }
+
         TransferFunction {
             tcx: self.tcx,
             info: self.info,
-            contexts: self.contexts.clone(),
+            config: self.config,
+            body: self.body,
+            shared: self.shared.clone(),
             state: &mut PointsAwareTaintDomain {
-                state,
+                state: &mut state.tainted,
                 map: &mut self.points.borrow_mut(),
             },
+            location,
+            tainted_branches: &self.tainted_branches,
+            tainted_tests: &self.tainted_tests,
+            origins: &mut state.origins,
+            statics: &self.statics,
+            init: &self.init,
         }
         .visit_terminator(terminator, location);
+
+        if let Some(convergence) = &self.convergence {
+            convergence
+                .borrow_mut()
+                .record(location.block, state.tainted.count());
+        }
     }
 
     fn apply_call_return_effect(
         &self,
         _state: &mut Self::Domain,
         _block: BasicBlock,
-        _func: &Operand<'intra>,
-        _args: &[Operand<'intra>],
-        _return_place: Place<'intra>,
+        _func: &Operand<'tcx>,
+        _args: &[Operand<'tcx>],
+        _return_place: Place<'tcx>,
     ) {
         // do nothing
     }
@@ -161,25 +535,49 @@ impl<'tcx> std::fmt::Debug for TransferFunction<'_, '_, '_> {
     }
 }
 
-impl<'inter> Visitor<'inter> for TransferFunction<'_, '_, '_> {
-    fn visit_statement(&mut self, statement: &Statement<'inter>, _: Location) {
+impl<'tcx> Visitor<'tcx> for TransferFunction<'tcx, '_, '_> {
+    fn visit_statement(&mut self, statement: &Statement<'tcx>, _: Location) {
+        let _span = debug_span!("statement", location = ?self.location).entered();
         let Statement { source_info, kind } = statement;
 
         self.visit_source_info(source_info);
 
-        if let StatementKind::Assign(box (ref place, ref rvalue)) = kind {
-            self.t_visit_assign(place, rvalue);
+        match kind {
+            StatementKind::Assign(box (ref place, ref rvalue)) => {
+                self.t_visit_assign(place, rvalue);
+            }
+
+            // These do not affect the values of any locals.
+            StatementKind::FakeRead(..)
+            | StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::Retag(..)
+            | StatementKind::AscribeUserType(..)
+            | StatementKind::Coverage(_)
+            | StatementKind::Nop => {}
+
+            // Building an enum variant assigns its fields, which taint the enum, and then sets its discriminant,
+            // which is a constant and does not clean them, so reading the discriminant of the enum is tainted as well.
+            StatementKind::SetDiscriminant { .. } => {}
+
+            StatementKind::LlvmInlineAsm(_) => self.t_unhandled("StatementKind::LlvmInlineAsm"),
+            StatementKind::CopyNonOverlapping(_) => {
+                self.t_unhandled("StatementKind::CopyNonOverlapping")
+            }
         }
     }
 
-    fn visit_terminator(&mut self, terminator: &Terminator<'inter>, _: Location) {
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, _: Location) {
+        let _span = debug_span!("terminator", location = ?self.location).entered();
         let Terminator { source_info, kind } = terminator;
 
         self.visit_source_info(source_info);
 
         match kind {
             TerminatorKind::Goto { .. } => {}
+            // Which branches are taken on tainted data is found once the iteration converges, in `into_results`.
             TerminatorKind::SwitchInt { .. } => {}
+            // The state at each return is joined into the summary by `into_exit_state`.
             TerminatorKind::Return => {}
             TerminatorKind::Call {
                 func: Operand::Constant(ref c),
@@ -189,93 +587,575 @@ impl<'inter> Visitor<'inter> for TransferFunction<'_, '_, '_> {
                 ..
             } => {
                 self.t_visit_call(c, args, destination, fn_span);
+                self.t_visit_moved_args(args, destination);
             }
-            TerminatorKind::Assert { .. } => {}
-            _ => {}
+            // The effect of a terminator reaches its cleanup successor as well,
+            // so `Assert` and `Drop` must only report, and not change any taint,
+            // for the cleanup block to see the state from before them.
+            TerminatorKind::Assert { msg, .. } => {
+                if self.config.enabled(AnalysisOption::TaintedIndexIsSink) {
+                    if let AssertKind::BoundsCheck { index, .. } = msg {
+                        self.t_visit_bounds_check(index, &source_info.span);
+                    }
+                }
+            }
+
+            // These only affect control flow.
+            TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Unreachable
+            | TerminatorKind::GeneratorDrop
+            | TerminatorKind::FalseEdge { .. }
+            | TerminatorKind::FalseUnwind { .. } => {}
+
+            TerminatorKind::Call { .. } => self.t_unhandled("TerminatorKind::Call (indirect)"),
+            TerminatorKind::Drop { .. } => self.t_unhandled("TerminatorKind::Drop"),
+            TerminatorKind::DropAndReplace { .. } => {
+                self.t_unhandled("TerminatorKind::DropAndReplace")
+            }
+            TerminatorKind::Yield { .. } => self.t_unhandled("TerminatorKind::Yield"),
+            TerminatorKind::InlineAsm { .. } => self.t_unhandled("TerminatorKind::InlineAsm"),
         }
     }
 }
 
-impl<'long> TransferFunction<'_, '_, '_>
-where
-    Self: Visitor<'long>,
-{
+impl TransferFunction<'_, '_, '_> {
+    /// Whether `local` may be tainted at the current location.
+    pub fn is_tainted(&self, local: Local) -> bool {
+        self.state.get_taint(local)
+    }
+
+    /// Taint or clean `local`, along with the locals it points to.
+    pub fn set_taint(&mut self, local: Local, tainted: bool) {
+        self.state.set_taint(local, tainted);
+    }
+}
+
+impl<'tcx> TransferFunction<'tcx, '_, '_> {
     #[instrument]
-    fn t_visit_assign(&mut self, place: &Place, rvalue: &Rvalue) {
+    fn t_visit_assign(&mut self, place: &Place<'tcx>, rvalue: &Rvalue<'tcx>) {
+        // Fields are not tracked separately, so assigning to a projection
+        // only overwrites part of the local and must not clear its taint.
+        let keep_taint = !place.projection.is_empty() && self.state.get_taint(place.local);
+
+        // The value is overwritten, so it only keeps an origin if it is propagated below.
+        if place.projection.is_empty() {
+            self.origins.remove(&place.local);
+        }
+
         match rvalue {
-            // If we assign a constant to a place, the place is clean.
-            Rvalue::Use(Operand::Constant(_)) | Rvalue::UnaryOp(_, Operand::Constant(_)) => {
+            // Function items and closures without captures are determined by their type,
+            // so they are clean even when assigned in a tainted branch.
+            // Closures with captures are built field by field, which propagates the taint of the captures.
+            Rvalue::Use(Operand::Constant(c))
+                if matches!(c.ty().kind(), TyKind::FnDef(..) | TyKind::Closure(..)) =>
+            {
                 self.state.set_taint(place.local, false)
             }
 
-            // Otherwise we propagate the taint
-            Rvalue::Use(Operand::Copy(f) | Operand::Move(f)) => {
-                self.state.propagate(f.local, place.local);
+            // If we assign a constant to a place, the place is clean,
+            // unless the choice of constant depends on a tainted value.
+            // A boolean which every branch of a test on a tainted value assigns, such as the result of `matches!`,
+            // is the result of a comparison, and is tainted unless comparisons are clean,
+            // even without implicit flows.
+            // Constant aggregates, such as tuples with references, may point into a tainted static.
+            // Statics are accessed through their address, which is tainted if a tainted value
+            // has been written to the static anywhere in the crate.
+            Rvalue::Use(Operand::Constant(c)) | Rvalue::UnaryOp(_, Operand::Constant(c)) => {
+                let tainted = if let Some(id) = c.check_static_ptr(self.tcx) {
+                    self.statics.borrow_mut().insert(place.local, id);
+                    self.t_references_tainted_static(c)
+                } else {
+                    self.t_is_implicit_flow()
+                        || (c.ty().is_bool()
+                            && place.projection.is_empty()
+                            && self.tainted_tests.get(&self.location.block) == Some(&place.local))
+                        || self.t_references_tainted_static(c)
+                };
+                self.state.set_taint(place.local, tainted)
             }
 
-            Rvalue::BinaryOp(_, box b) | Rvalue::CheckedBinaryOp(_, box b) => match b {
-                (Operand::Constant(_), Operand::Constant(_)) => {
-                    self.state.set_taint(place.local, false);
+            // Otherwise we propagate the taint.
+            // Only the local of a place is tracked, so reading through projections such as
+            // fields, downcasts and the elements which slice patterns bind by constant index or subslice,
+            // or coercing to an opaque type, keeps its taint.
+            // This also holds for the fields of a union, which is tainted by a write to any of them,
+            // since they share its storage.
+            Rvalue::Use(Operand::Copy(f) | Operand::Move(f)) => {
+                self.t_propagate(f.local, place.local);
+
+                // The provenance of raw pointers is not tracked, so they may point to anything.
+                if self.config.enabled(AnalysisOption::UnsafeConservative)
+                    && self.t_derefs_raw_pointer(f)
+                {
+                    self.state.set_taint(place.local, true);
                 }
-                (Operand::Copy(a) | Operand::Move(a), Operand::Copy(b) | Operand::Move(b)) => {
-                    if self.state.get_taint(a.local) || self.state.get_taint(b.local) {
-                        self.state.set_taint(place.local, true);
-                    } else {
-                        self.state.set_taint(place.local, false);
+            }
+
+            // The result of a comparison reveals little about its operands, and may be considered clean.
+            Rvalue::BinaryOp(op, _) | Rvalue::CheckedBinaryOp(op, _)
+                if self.config.enabled(AnalysisOption::CleanComparisons) && is_comparison(*op) =>
+            {
+                self.state.set_taint(place.local, false);
+            }
+
+            Rvalue::BinaryOp(_, box b) | Rvalue::CheckedBinaryOp(_, box b) => {
+                match b {
+                    (Operand::Constant(_), Operand::Constant(_)) => {
+                        self.state.set_taint(place.local, self.t_is_implicit_flow());
+                    }
+                    (Operand::Copy(a) | Operand::Move(a), Operand::Copy(b) | Operand::Move(b)) => {
+                        if self.state.get_taint(a.local) {
+                            self.t_propagate(a.local, place.local);
+                        } else if self.state.get_taint(b.local) {
+                            self.t_propagate(b.local, place.local);
+                        } else {
+                            self.state.set_taint(place.local, false);
+                        }
+                    }
+                    (Operand::Copy(p) | Operand::Move(p), Operand::Constant(_))
+                    | (Operand::Constant(_), Operand::Copy(p) | Operand::Move(p)) => {
+                        self.t_propagate(p.local, place.local);
                     }
                 }
-                (Operand::Copy(p) | Operand::Move(p), Operand::Constant(_))
-                | (Operand::Constant(_), Operand::Copy(p) | Operand::Move(p)) => {
-                    self.state.propagate(p.local, place.local);
+
+                // Moved operands are no longer initialized, so they no longer carry taint.
+                for operand in [&b.0, &b.1].iter() {
+                    if let Operand::Move(moved) = operand {
+                        if moved.projection.is_empty() && moved.local != place.local {
+                            self.state.state.set_taint(moved.local, false);
+                        }
+                    }
                 }
-            },
+            }
             Rvalue::UnaryOp(_, Operand::Move(p) | Operand::Copy(p)) => {
-                self.state.propagate(p.local, place.local);
+                self.t_propagate(p.local, place.local);
             }
             Rvalue::Ref(_region_kind, _borrow_kind, p) => {
                 self.state.add_ref(place, p);
+
+                // A reference to tainted data is tainted as well.
+                // Fields and elements are not tracked separately, so a reference to a field such as `&s.field`,
+                // or to a subslice such as `rest` in `[first, rest @ ..]`, carries the taint of the whole of `s`.
+                // This is also how the receiver of a method taking `&self` is passed, which is borrowed into a
+                // temporary first, so the models of methods see the taint of the receiver on their first argument.
+                if self.state.get_taint(p.local) {
+                    self.t_propagate(p.local, place.local);
+                }
+            }
+
+            // Numeric casts, enum to integer casts and casts between pointers and integers
+            // keep the taint of the value.
+            Rvalue::Cast(CastKind::Misc, operand, ty) => {
+                let tainted = match operand.place() {
+                    Some(p) => {
+                        self.t_propagate(p.local, place.local);
+                        self.state.get_taint(p.local)
+                    }
+                    None => {
+                        self.state.set_taint(place.local, self.t_is_implicit_flow());
+                        false
+                    }
+                };
+
+                // Provenance of the pointer is not tracked.
+                if self.config.enabled(AnalysisOption::NotePointerCasts)
+                    && !self.shared.borrow().silent
+                    && tainted
+                    && is_pointer_int_cast(operand.ty(self.body, self.tcx), ty)
+                {
+                    let span = self.body.source_info(self.location).span;
+                    self.tcx.sess.span_note_without_error(
+                        span,
+                        "tainted value cast between pointer and integer, its provenance is not tracked",
+                    );
+                }
+            }
+
+            Rvalue::Repeat(_, _) => self.t_visit_modeled(place, rvalue, "Rvalue::Repeat"),
+            Rvalue::ThreadLocalRef(_) => {
+                self.t_visit_modeled(place, rvalue, "Rvalue::ThreadLocalRef")
+            }
+            Rvalue::AddressOf(_, _) => self.t_visit_modeled(place, rvalue, "Rvalue::AddressOf"),
+            Rvalue::Len(p) if self.config.enabled(AnalysisOption::TaintLen) => {
+                self.t_propagate(p.local, place.local)
+            }
+            Rvalue::Len(_) => self.t_visit_modeled(place, rvalue, "Rvalue::Len"),
+            // Unsizing, such as of an array to a slice, and the other pointer casts keep the referent.
+            Rvalue::Cast(CastKind::Pointer(_), Operand::Copy(p) | Operand::Move(p), _) => {
+                self.t_propagate(p.local, place.local)
+            }
+            Rvalue::Cast(_, _, _) => self.t_visit_modeled(place, rvalue, "Rvalue::Cast"),
+            Rvalue::NullaryOp(_, _) => self.t_visit_modeled(place, rvalue, "Rvalue::NullaryOp"),
+            Rvalue::Discriminant(p) => self.t_propagate(p.local, place.local),
+            // Optimized MIR builds tuples, structs and closures field by field,
+            // but arrays, such as the arguments of `format_args!`, are built whole.
+            // Enum variants and structs are usually built field by field, but may be built whole like arrays,
+            // and are tainted if any of their fields is. Their discriminant is part of the kind, and is a constant.
+            Rvalue::Aggregate(
+                box AggregateKind::Array(_) | box AggregateKind::Adt(..),
+                operands,
+            ) => {
+                match operands
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .find(|element| self.state.get_taint(element.local))
+                {
+                    Some(element) => self.t_propagate(element.local, place.local),
+                    None => self.state.set_taint(place.local, false),
+                }
+            }
+            Rvalue::Aggregate(_, _) => self.t_visit_modeled(place, rvalue, "Rvalue::Aggregate"),
+        }
+
+        // Constructing a value of a tainted type taints it, regardless of its fields.
+        if keep_taint || self.t_has_tainted_type(place.local) {
+            self.state.set_taint(place.local, true);
+        }
+
+        // The value may have been changed on its way from the source, such as by sanitizing arithmetic.
+        if matches!(
+            rvalue,
+            Rvalue::BinaryOp(..)
+                | Rvalue::CheckedBinaryOp(..)
+                | Rvalue::UnaryOp(..)
+                | Rvalue::Cast(..)
+                | Rvalue::Len(_)
+        ) {
+            self.t_weaken(place.local, Confidence::Medium);
+        }
+
+        // Writing a tainted value through the address of a static taints the static.
+        if matches!(place.projection.first(), Some(ProjectionElem::Deref))
+            && self.state.get_taint(place.local)
+        {
+            if let Some(id) = self.statics.borrow().get(&place.local) {
+                self.shared.borrow_mut().tainted_statics.insert(*id);
+            }
+        }
+    }
+
+    /// Whether the value of `c` points to a tainted static, directly or through the values
+    /// of the statics it points to, such as a static tuple holding a reference to a tainted one.
+    fn t_references_tainted_static(&self, c: &Constant<'tcx>) -> bool {
+        let value = match c.literal {
+            ConstantKind::Ty(constant) => match constant.val {
+                ConstKind::Value(value) => value,
+                _ => return false,
+            },
+            ConstantKind::Val(value, _) => value,
+        };
+        let mut pending = match value {
+            ConstValue::Scalar(Scalar::Ptr(ptr)) => vec![ptr.alloc_id],
+            ConstValue::Slice { data: alloc, .. } | ConstValue::ByRef { alloc, .. } => {
+                alloc.relocations().values().map(|(_, id)| *id).collect()
             }
+            ConstValue::Scalar(_) => return false,
+        };
+
+        let tainted_statics = &self.shared.borrow().tainted_statics;
+        let mut seen = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+
+            // Only statics of the current crate may be tainted, or point to tainted ones.
+            let alloc = match self.tcx.get_global_alloc(id) {
+                Some(GlobalAlloc::Static(static_id)) if tainted_statics.contains(&static_id) => {
+                    return true;
+                }
+                Some(GlobalAlloc::Static(static_id))
+                    if static_id.is_local() && !self.tcx.is_foreign_item(static_id) =>
+                {
+                    match self.tcx.eval_static_initializer(static_id) {
+                        Ok(alloc) => alloc,
+                        Err(_) => continue,
+                    }
+                }
+                Some(GlobalAlloc::Memory(alloc)) => alloc,
+                _ => continue,
+            };
+            pending.extend(alloc.relocations().values().map(|(_, id)| *id));
+        }
+        false
+    }
+
+    /// Whether `place` is read through a raw pointer, such as `*p` or `(*p).0`.
+    fn t_derefs_raw_pointer(&self, place: &Place) -> bool {
+        matches!(place.projection.first(), Some(ProjectionElem::Deref))
+            && self.body.local_decls[place.local].ty.is_unsafe_ptr()
+    }
+
+    /// Let the registered models handle an rvalue which is not handled above.
+    fn t_visit_modeled(&mut self, place: &Place, rvalue: &Rvalue, construct: &'static str) {
+        let models = self.config.rvalue_models();
+        if !models.iter().any(|model| model.handle(place, rvalue, self)) {
+            self.t_unhandled(construct);
+        }
+    }
+
+    /// Propagate the taint of `from` to `to`, along with where it was introduced.
+    fn t_propagate(&mut self, from: Local, to: Local) {
+        self.state.propagate(from, to);
+
+        if let Some(origins) = self.origins.get(&from).cloned() {
+            self.origins.insert(to, origins);
+        }
+    }
 
-            Rvalue::Repeat(_, _) => {}
-            Rvalue::ThreadLocalRef(_) => {}
-            Rvalue::AddressOf(_, _) => {}
-            Rvalue::Len(_) => {}
-            Rvalue::Cast(_, _, _) => {}
-            Rvalue::NullaryOp(_, _) => {}
-            Rvalue::Discriminant(_) => {}
-            Rvalue::Aggregate(_, _) => {}
+    /// Lower the confidence in the taint of `local` to at most `confidence`.
+    fn t_weaken(&mut self, local: Local, confidence: Confidence) {
+        if let Some(origins) = self.origins.get_mut(&local) {
+            *origins = origins
+                .iter()
+                .map(|&(c, span)| (c.min(confidence), span))
+                .collect();
         }
     }
 
+    /// The origin of the taint of `local` along the most direct path to the current location, if it is known.
+    fn t_origin(&self, local: Local) -> Option<(Span, Confidence)> {
+        self.origins
+            .get(&local)?
+            .iter()
+            .max_by(|(a, a_span), (b, b_span)| a.cmp(b).then(b_span.cmp(a_span)))
+            .map(|&(confidence, span)| (span, confidence))
+    }
+
+    /// Record that the taint of `local` was introduced at `span`, replacing its other origins.
+    fn t_set_origin(&mut self, local: Local, span: Span, confidence: Confidence) {
+        self.origins
+            .insert(local, std::iter::once((confidence, span)).collect());
+    }
+
+    /// Whether the current block only runs depending on tainted data.
+    fn t_in_tainted_branch(&self) -> bool {
+        self.tainted_branches.contains(&self.location.block)
+    }
+
+    /// Whether the constants assigned in the current block are tainted by the branch it is in,
+    /// since they reveal which branch was taken.
+    fn t_is_implicit_flow(&self) -> bool {
+        self.config.enabled(AnalysisOption::ImplicitFlows) && self.t_in_tainted_branch()
+    }
+
     #[instrument]
     fn t_visit_call(
         &mut self,
-        func: &Constant,
-        args: &[Operand],
+        func: &Constant<'tcx>,
+        args: &[Operand<'tcx>],
         destination: &Option<(Place, BasicBlock)>,
         span: &Span,
     ) {
         let name = func.to_string();
-        let id = match func.literal.ty().kind() {
-            TyKind::FnDef(id, _args) => Some(id),
+        let (id, substs) = match func.literal.ty().kind() {
+            TyKind::FnDef(id, substs) => Some((id, substs)),
             _ => None,
         }
         .unwrap();
 
-        match self.info.get_kind(id) {
-            Some(AttrInfoKind::Source) => self.t_visit_source_destination(destination),
+        self.shared
+            .borrow_mut()
+            .calls
+            .insert((self.body.source.def_id(), *id, *span));
+
+        if let Some((place, _)) = destination {
+            if place.projection.is_empty() {
+                self.origins.remove(&place.local);
+            }
+        }
+
+        let path = self.tcx.def_path_str(*id);
+        if let Some(spec) = self.config.source(&path) {
+            return self.t_visit_configured_source(spec, args, destination, span);
+        }
+        if let Some(spec) = self.config.flow(&path) {
+            return self.t_visit_flow_spec(spec, args, destination);
+        }
+        if IDENTITY_METHODS.contains(&path.as_str()) {
+            return self.t_visit_identity(args, destination);
+        }
+        if COPY_METHODS.contains(&path.as_str()) {
+            // Calls through the trait are otherwise unknown calls, since the trait's method has no body.
+            return match self.t_hand_written(id, substs) {
+                Some(method) => self.t_fn_call_analysis(args, &method, destination, span),
+                None => self.t_visit_identity(args, destination),
+            };
+        }
+        if SLICE_INDEX_METHODS.contains(&path.as_str()) {
+            // The index is the receiver, and the slice comes after it.
+            return self.t_visit_identity(&args[1..], destination);
+        }
+        if self.t_is_try_conversion(id) {
+            return self.t_visit_identity(args, destination);
+        }
+        if self.t_is_mem_exchange(id) {
+            return self.t_visit_mem_exchange(id, args, destination);
+        }
+        if FORMAT_FUNCTIONS.contains(&path.as_str()) {
+            return self.t_visit_format(args, destination);
+        }
+        if UNWRAP_OR_METHODS.contains(&path.as_str()) {
+            return self.t_visit_unwrap_or(args, destination);
+        }
+
+        // Calls through a trait refer to the trait's method, rather than the annotated implementation.
+        let annotated = Some(*id)
+            .filter(|id| self.info.get_kind(id).is_some())
+            .or_else(|| self.t_resolve(id, substs))
+            .unwrap_or(*id);
+        let kind = self.info.get_kind(&annotated);
+        debug!("call to `{}` ({:?})", name, kind);
+
+        let filter = self.shared.borrow().categories.clone();
+        match kind {
+            // Sources of other categories are matched in their own round, and are clean in this one.
+            Some(AttrInfoKind::Source) if !self.info.source_matches(&annotated, &filter) => {
+                self.t_visit_sanitizer_destination(destination)
+            }
+            Some(AttrInfoKind::Source) => self.t_visit_source_destination(destination, span),
             Some(AttrInfoKind::Sanitizer) => self.t_visit_sanitizer_destination(destination),
-            Some(AttrInfoKind::Sink) => self.t_visit_sink(name, args, span),
-            None => self.t_fn_call_analysis(args, id, destination),
+            Some(AttrInfoKind::Sink) if !self.info.sink_matches(&annotated, &filter) => {}
+            Some(AttrInfoKind::Sink) => {
+                let status = if self.t_visit_sink(name, args, span) {
+                    SinkStatus::Violated
+                } else {
+                    SinkStatus::Clean
+                };
+                let mut shared = self.shared.borrow_mut();
+                let reached = shared.sinks.entry(annotated).or_insert(status);
+                *reached = (*reached).max(status);
+            }
+            None => {
+                // The panic machinery of the standard library calls other such functions,
+                // which must not be reported again.
+                if self.config.enabled(AnalysisOption::PanicMessageIsSink)
+                    && self.t_is_panic_with_message(id)
+                {
+                    return self.t_visit_panic_message(&name, args, span);
+                }
+                // Like a sink without categories, these are only checked in the round for those.
+                if self.config.enabled(AnalysisOption::OutputSinks)
+                    && OUTPUT_SINKS.contains(&path.as_str())
+                    && !matches!(filter, CategoryFilter::Only(_))
+                {
+                    self.t_visit_sink(name, args, &span.source_callsite());
+                    return;
+                }
+                if self.t_is_write_fmt(id) {
+                    return self.t_visit_write_fmt(name, args, destination, span);
+                }
+                if self.config.enabled(AnalysisOption::PanicOnTaintedIsSink)
+                    && self.t_is_panicking_unwrap(id)
+                {
+                    self.t_visit_panicking_unwrap(name, args, span);
+                }
+                self.t_record_callback(id, args);
+                self.t_visit_closure_args(substs, args);
+                if let Some(generator) = self.t_awaited_generator(id, substs) {
+                    self.t_visit_await(&generator, args, destination, span);
+                } else if let Some(collection) = self.t_collection_of_method(id, substs) {
+                    self.t_visit_collection_call(id, collection, args, destination);
+                } else if self.t_is_iterator_method(id) {
+                    self.t_visit_iterator_call(args, destination);
+                } else {
+                    self.t_fn_call_analysis(args, id, destination, span);
+                }
+
+                if self.config.enabled(AnalysisOption::Channels) {
+                    self.t_visit_channel(id, substs, args, destination, span);
+                }
+
+                // The callee is still analyzed, so that sinks within the boundary are checked.
+                if self.t_crosses_sanitizer_boundary(&annotated) {
+                    self.t_visit_sanitizer_destination(destination);
+                }
+
+                if let Some((place, _)) = destination {
+                    if self.t_has_tainted_type(place.local) {
+                        self.state.set_taint(place.local, true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The function which is called for `id` instantiated with `substs`, if it is known.
+    fn t_resolve(&self, id: &DefId, substs: SubstsRef<'tcx>) -> Option<DefId> {
+        let param_env = self.tcx.param_env(self.body.source.def_id());
+        match Instance::resolve(self.tcx, param_env, *id, substs) {
+            Ok(Some(instance)) => Some(instance.def_id()),
+            _ => None,
+        }
+    }
+
+    /// The method which `id` resolves to with `substs`, if it is implemented by hand outside of the standard library,
+    /// rather than derived.
+    fn t_hand_written(&self, id: &DefId, substs: SubstsRef<'tcx>) -> Option<DefId> {
+        let method = self.t_resolve(id, substs)?;
+        let std = matches!(
+            &*self.tcx.crate_name(method.krate).as_str(),
+            "std" | "core" | "alloc"
+        );
+        let derived = self.tcx.impl_of_method(method).map_or(false, |impl_id| {
+            self.tcx.has_attr(impl_id, sym::automatically_derived)
+        });
+        (!std && !derived).then(|| method)
+    }
+
+    /// Whether `id` is within one of the modules or types configured with `--taint-sanitizer-boundary`,
+    /// and the current body is not. Methods of trait implementations are within the boundary of their type.
+    fn t_crosses_sanitizer_boundary(&self, id: &DefId) -> bool {
+        if self.config.paths(PathList::SanitizerBoundaries).is_empty() {
+            return false;
+        }
+
+        let within = |id: DefId| {
+            let mut paths = vec![self.tcx.def_path_str(id)];
+            if let Some(impl_id) = self.tcx.impl_of_method(id) {
+                if let TyKind::Adt(adt, _) = self.tcx.type_of(impl_id).kind() {
+                    paths.push(self.tcx.def_path_str(adt.did));
+                }
+            }
+            paths
+                .iter()
+                .any(|path| self.config.matches(PathList::SanitizerBoundaries, path))
+        };
+        within(*id) && !within(self.body.source.def_id())
+    }
+
+    /// Whether `local` is an instance of one of the types configured with `--taint-tainted-type`.
+    fn t_has_tainted_type(&self, local: Local) -> bool {
+        if self.config.paths(PathList::TaintedTypes).is_empty() {
+            return false;
+        }
+
+        match self.body.local_decls[local].ty.kind() {
+            TyKind::Adt(adt, _) => {
+                let path = self.tcx.def_path_str(adt.did);
+                self.config.matches(PathList::TaintedTypes, &path)
+            }
+            _ => false,
         }
     }
 
     fn t_fn_call_analysis(
         &mut self,
-        args: &[Operand],
+        args: &[Operand<'tcx>],
         id: &rustc_hir::def_id::DefId,
         destination: &Option<(Place, BasicBlock)>,
+        span: &Span,
     ) {
+        // Trait methods and foreign functions have no body for us to analyze.
+        if !self.tcx.is_mir_available(*id)
+            || self
+                .config
+                .matches(PathList::Ignored, &self.tcx.def_path_str(*id))
+        {
+            return self.t_unknown_call(args, destination);
+        }
+
         let init = args
             .iter()
             .map(|arg| match arg {
@@ -284,101 +1164,1456 @@ where
             })
             .collect::<Vec<_>>();
 
-        let end_state = self.t_function_summary(id, init);
+        if self.t_beyond_max_call_depth(id, &init) {
+            let path = self.tcx.def_path_str(*id);
+            self.shared.borrow_mut().depth_limited.insert(path);
+            return self.t_unknown_call(args, destination);
+        }
 
-        if let Some(end_state) = end_state {
-            let return_place = Local::from_usize(0);
+        let end_state = self.t_function_summary(id, init.clone());
+        self.t_visit_callbacks(id, init, args);
 
-            if end_state.get_taint(return_place) {
-                self.t_visit_source_destination(destination);
+        if let Some(end_state) = end_state {
+            let target_body = self.tcx.optimized_mir(*id);
+            // The callee may have written taint through the references it received, which reaches their referents.
+            // A copied value is not changed by the callee, even if it assigns to its parameter.
+            for (arg, callee_arg) in args.iter().zip(target_body.args_iter()) {
+                let tainted = end_state.get_taint(callee_arg);
+                match arg {
+                    Operand::Copy(place) => {
+                        let ty = self.body.local_decls[place.local].ty;
+                        if ty.is_ref() || ty.is_unsafe_ptr() {
+                            self.state.set_taint(place.local, tainted);
+                        }
+                    }
+                    // It is cleaned once the call has been handled, in `t_visit_moved_args`.
+                    Operand::Move(place) => self.state.set_taint(place.local, tainted),
+                    Operand::Constant(_) => {}
+                }
             }
 
-            let target_body = self.tcx.optimized_mir(*id);
-            let arg_map = args
-                .iter()
-                .map(|arg| arg.place().or(None))
-                .zip(target_body.args_iter())
-                .collect::<Vec<_>>();
+            // The callee's return place is our destination, which is written last,
+            // and is clean if the callee returns a clean value.
+            if end_state.get_taint(RETURN_PLACE) {
+                self.t_visit_source_destination(destination, span);
+                if let Some((place, _)) = destination {
+                    self.t_weaken(place.local, Confidence::Medium);
+                }
+            } else {
+                self.t_visit_sanitizer_destination(destination);
+            }
+        }
+    }
 
-            // Check if any variables which were passed in are tainted at this point.
-            for (caller_arg, callee_arg) in arg_map {
-                if let Some(place) = caller_arg {
-                    self.state
-                        .set_taint(place.local, end_state.get_taint(callee_arg));
+    /// Arguments moved into a call are no longer initialized once it returns, so they no longer carry taint,
+    /// unlike the locals they point to. Copied arguments are still live, and keep their taint.
+    fn t_visit_moved_args(&mut self, args: &[Operand], destination: &Option<(Place, BasicBlock)>) {
+        let returned = destination.as_ref().map(|(place, _)| place.local);
+        for arg in args {
+            if let Operand::Move(moved) = arg {
+                if moved.projection.is_empty() && Some(moved.local) != returned {
+                    self.state.state.set_taint(moved.local, false);
+                    self.origins.remove(&moved.local);
                 }
             }
         }
     }
 
-    fn t_function_summary(&mut self, id: &DefId, init: Vec<Option<bool>>) -> Option<BitSet<Local>> {
-        let key = (*id, init.clone());
+    /// Apply the configured policy to a call whose callee cannot be analyzed.
+    fn t_unknown_call(&mut self, args: &[Operand], destination: &Option<(Place, BasicBlock)>) {
+        if self.config.enabled(AnalysisOption::MutateArgsOnCall) {
+            self.t_mutate_args(args);
+        }
 
-        if let Some(summary) = self.t_get_cached_summary(&key) {
-            summary
-        } else {
-            // In the case that we have recursive or mutually recursive function calls,
-            // we make sure that we only compute a summary once per key by inserting None while we compute it.
-            // For subsequent calls, calling `t_function_summary` will simply return None and the visitor will analyze other branches.
-            self.t_insert_summary(&key, None);
+        let tainted = match self.config.unknown_call_policy() {
+            UnknownCallPolicy::PropagateArgsToReturn => args
+                .iter()
+                .filter_map(|arg| arg.place())
+                .any(|place| self.state.get_taint(place.local)),
+            UnknownCallPolicy::TaintReturn => true,
+            UnknownCallPolicy::Ignore => {
+                self.t_visit_sanitizer_destination(destination);
+                return;
+            }
+        };
 
-            let target_body = self.tcx.optimized_mir(*id);
-            let mut results =
-                TaintAnalysis::new_with_init(self.tcx, self.info, self.contexts.clone(), init)
-                    .into_engine(self.tcx, target_body)
-                    .pass_name("taint_analysis")
-                    .iterate_to_fixpoint()
-                    .into_results_cursor(target_body);
-
-            let state = if let Some((last, _)) = reverse_postorder(target_body).last() {
-                results.seek_to_block_end(last);
-                Some(results.get().clone())
-            } else {
-                None
-            };
+        if let Some((place, _)) = destination {
+            self.state.set_taint(place.local, tainted);
+        }
+    }
 
-            // Once the function summary has been computed, we insert it into the cache.
-            self.t_insert_summary(&key, state.clone());
+    /// The callee may write tainted arguments through any reference it receives,
+    /// so taint the referents of all reference arguments if any argument is tainted.
+    fn t_mutate_args(&mut self, args: &[Operand]) {
+        let places = args
+            .iter()
+            .filter_map(|arg| arg.place())
+            .collect::<Vec<_>>();
+        if !places.iter().any(|place| self.state.get_taint(place.local)) {
+            return;
+        }
 
-            state
+        for place in places {
+            let ty = self.body.local_decls[place.local].ty;
+            if ty.is_ref() || ty.is_unsafe_ptr() {
+                // Tainting the reference taints the locals it points to as well.
+                self.state.set_taint(place.local, true);
+            }
         }
     }
 
-    fn t_insert_summary(&mut self, key: &(DefId, Vec<Option<bool>>), val: Option<BitSet<Local>>) {
-        self.contexts.borrow_mut().insert(key.clone(), val);
+    /// Whether the summary of `id` for `init` would have to be computed deeper than `max_call_depth`,
+    /// counting the summaries which are being computed. Summaries which were already computed are still used,
+    /// even if the calls they were computed for were cut off at a shallower depth.
+    fn t_beyond_max_call_depth(&self, id: &DefId, init: &[Option<bool>]) -> bool {
+        let shared = self.shared.borrow();
+        match self.config.max_call_depth() {
+            Some(max) => {
+                shared.depth >= max && !shared.contexts.contains_key(&(*id, init.to_vec()))
+            }
+            None => false,
+        }
+    }
+
+    fn t_function_summary(&mut self, id: &DefId, init: Vec<Option<bool>>) -> Option<BitSet<Local>> {
+        let key = (*id, init.clone());
+
+        if let Some(summary) = self.t_get_cached_summary(&key) {
+            return summary;
+        }
+
+        // Recursive calls, directly or through other functions, see the assumed summary while it is computed.
+        // If the computed summary says more than the assumption, it is computed again with both joined,
+        // which only adds taint, so it stops once all of the arguments and the return value are tainted.
+        let target_body = self.tcx.optimized_mir(*id);
+        let mut assumed = self.t_assumed_summary(target_body, &init);
+        loop {
+            let computed_before = self
+                .shared
+                .borrow()
+                .contexts
+                .keys()
+                .cloned()
+                .collect::<HashSet<_>>();
+            self.t_insert_summary(&key, Some(assumed.clone()));
+
+            self.shared.borrow_mut().depth += 1;
+            let state = TaintAnalysis::new_with_init(
+                self.tcx,
+                self.info,
+                self.config,
+                target_body,
+                self.shared.clone(),
+                init.clone(),
+            )
+            .into_exit_state();
+            self.shared.borrow_mut().depth -= 1;
+
+            let grown = state.as_ref().map_or(false, |state| {
+                summary_locals(target_body)
+                    .any(|local| state.contains(local) && !assumed.contains(local))
+            });
+            if !grown {
+                // Once the function summary has been computed, we insert it into the cache.
+                self.t_insert_summary(&key, state.clone());
+                return state;
+            }
+
+            // Summaries computed under the old assumption, such as those of mutually recursive functions, are stale.
+            self.shared
+                .borrow_mut()
+                .contexts
+                .retain(|key, _| computed_before.contains(key));
+            for local in summary_locals(target_body) {
+                if state.as_ref().map_or(false, |state| state.contains(local)) {
+                    assumed.insert(local);
+                }
+            }
+        }
+    }
+
+    /// The summary which recursive calls to `body` see before its summary has been computed.
+    /// The arguments keep the taint they are passed with, and the return value depends on `recursion`.
+    fn t_assumed_summary(&self, body: &Body, init: &[Option<bool>]) -> BitSet<Local> {
+        let mut assumed = BitSet::new_empty(body.local_decls.len());
+        for (arg, tainted) in body.args_iter().zip(init.iter()) {
+            if *tainted == Some(true) {
+                assumed.insert(arg);
+            }
+        }
+
+        let tainted_args = init.iter().any(|tainted| *tainted == Some(true));
+        if self.config.recursion() == RecursionAssumption::Pessimistic && tainted_args {
+            assumed.insert(RETURN_PLACE);
+        }
+        assumed
+    }
+
+    fn t_insert_summary(&mut self, key: &(DefId, Vec<Option<bool>>), val: Option<BitSet<Local>>) {
+        self.shared.borrow_mut().contexts.insert(key.clone(), val);
     }
 
     fn t_get_cached_summary(
         &mut self,
         key: &(DefId, Vec<Option<bool>>),
     ) -> Option<Option<BitSet<Local>>> {
-        let contexts = self.contexts.borrow();
-        contexts.get(key).map(|res| res.clone())
+        let shared = self.shared.borrow();
+        shared.contexts.get(key).cloned()
+    }
+
+    /// A generic function such as `Option::map` calls its closure argument through a trait method,
+    /// without knowing its type. Remember which argument it calls, so that the caller,
+    /// which knows the closure, analyzes the closure's body with the taint of the call.
+    fn t_record_callback(&mut self, id: &DefId, args: &[Operand<'tcx>]) {
+        let is_fn_trait = self.tcx.trait_of_item(*id).map_or(false, |trait_id| {
+            self.tcx.fn_trait_kind_from_lang_item(trait_id).is_some()
+        });
+        let callee = match args.first().and_then(|arg| arg.place()) {
+            Some(callee) if is_fn_trait => callee,
+            _ => return,
+        };
+
+        let ty = callee.ty(self.body, self.tcx).ty.peel_refs();
+        if let Some(index) = self.t_param_arg(ty) {
+            // The arguments are passed as a tuple, after the closure.
+            let tainted = args[1..]
+                .iter()
+                .filter_map(|arg| arg.place())
+                .any(|arg| self.state.get_taint(arg.local));
+            self.t_insert_callback(index, tainted);
+        }
+    }
+
+    /// Analyze the closures which a summarized function calls, in the context of the call.
+    fn t_visit_callbacks(&mut self, id: &DefId, init: InitSet, args: &[Operand<'tcx>]) {
+        let callbacks = self
+            .shared
+            .borrow()
+            .callbacks
+            .get(&(*id, init))
+            .cloned()
+            .unwrap_or_default();
+
+        for (index, tainted) in callbacks {
+            let arg = match args.get(index).and_then(|arg| arg.place()) {
+                Some(arg) => arg,
+                None => continue,
+            };
+
+            let ty = arg.ty(self.body, self.tcx).ty.peel_refs();
+            match ty.kind() {
+                // The captures are tainted if the closure is.
+                TyKind::Closure(closure_id, _) => {
+                    let arg_count = self.tcx.optimized_mir(*closure_id).arg_count;
+                    let mut init = vec![Some(self.state.get_taint(arg.local))];
+                    init.resize(arg_count, Some(tainted));
+                    let _ = self.t_function_summary(closure_id, init);
+                }
+                // The closure is passed on from our own caller, which knows its type.
+                TyKind::Param(_) => {
+                    if let Some(own) = self.t_param_arg(ty) {
+                        self.t_insert_callback(own, tainted);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The argument of the current body whose type, behind references, is the type parameter `ty`.
+    fn t_param_arg(&self, ty: Ty<'tcx>) -> Option<usize> {
+        if !matches!(ty.kind(), TyKind::Param(_)) {
+            return None;
+        }
+        self.body
+            .args_iter()
+            .position(|arg| self.body.local_decls[arg].ty.peel_refs() == ty)
+    }
+
+    fn t_insert_callback(&mut self, index: usize, tainted: bool) {
+        let key = (self.body.source.def_id(), self.init.clone());
+        self.shared
+            .borrow_mut()
+            .callbacks
+            .entry(key)
+            .or_default()
+            .insert((index, tainted));
+    }
+
+    /// Closures and generators are called through traits or library functions, such as `Fn::call` or `thread::spawn`,
+    /// which we cannot follow into the closure body.
+    /// Instead, we remember whether the operand which holds a closure of a call instantiated with it is tainted,
+    /// and whether any other operand is, so that the captures and the arguments of the closure
+    /// can be tainted when its body is analyzed.
+    fn t_visit_closure_args(&mut self, substs: SubstsRef<'tcx>, args: &[Operand<'tcx>]) {
+        for ty in substs.types() {
+            let id = match ty.kind() {
+                TyKind::Closure(id, _) | TyKind::Generator(id, _, _) => *id,
+                _ => continue,
+            };
+
+            // The closure may be passed wrapped, such as a generator in the future of an `async` block.
+            let (mut captures, mut arguments) = (false, false);
+            for arg in args {
+                let tainted = arg
+                    .place()
+                    .map_or(false, |place| self.state.get_taint(place.local));
+                if arg
+                    .ty(self.body, self.tcx)
+                    .walk()
+                    .any(|inner| inner == ty.into())
+                {
+                    captures |= tainted;
+                } else {
+                    arguments |= tainted;
+                }
+            }
+
+            let mut shared = self.shared.borrow_mut();
+            let tainted = shared.captures.entry(id).or_default();
+            tainted.0 |= captures;
+            tainted.1 |= arguments;
+        }
     }
 
-    fn t_visit_source_destination(&mut self, destination: &Option<(Place, BasicBlock)>) {
+    fn t_visit_source_destination(
+        &mut self,
+        destination: &Option<(Place, BasicBlock)>,
+        span: &Span,
+    ) {
         if let Some((place, _)) = destination {
             self.state.set_taint(place.local, true);
+            self.t_set_origin(place.local, *span, Confidence::High);
         }
     }
 
-    fn t_visit_sanitizer_destination(&mut self, destination: &Option<(Place, BasicBlock)>) {
+    /// Taint only the outputs of a source given by `--taint-source`, such as the buffer of `read_line`.
+    fn t_visit_configured_source(
+        &mut self,
+        spec: &SourceSpec,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+        span: &Span,
+    ) {
+        if spec.taints_return {
+            self.t_visit_source_destination(destination, span);
+        } else {
+            self.t_visit_sanitizer_destination(destination);
+        }
+
+        for place in spec
+            .out_args
+            .iter()
+            .filter_map(|index| args.get(*index)?.place())
+        {
+            // Tainting the reference taints the locals it points to as well.
+            self.state.set_taint(place.local, true);
+            self.t_set_origin(place.local, *span, Confidence::High);
+        }
+    }
+
+    /// Apply the rules given by `--taint-flow` instead of analyzing the callee.
+    fn t_visit_flow_spec(
+        &mut self,
+        spec: &FlowSpec,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+    ) {
+        // The taint of the arguments before the call, so the order of the rules does not matter.
+        let tainted = args
+            .iter()
+            .map(|arg| {
+                arg.place()
+                    .filter(|place| self.state.get_taint(place.local))
+                    .map(|place| place.local)
+            })
+            .collect::<Vec<_>>();
+
         if let Some((place, _)) = destination {
             self.state.set_taint(place.local, false);
         }
+
+        for flow in spec.flows.iter() {
+            let from = match tainted.get(flow.from) {
+                Some(Some(from)) => *from,
+                _ => continue,
+            };
+            let to = match flow.to {
+                FlowTarget::Return => destination.as_ref().map(|(place, _)| place.local),
+                // Tainting the reference taints the locals it points to as well.
+                FlowTarget::Arg(index) => args
+                    .get(index)
+                    .and_then(|arg| arg.place())
+                    .map(|place| place.local),
+            };
+            if let Some(to) = to {
+                self.t_propagate(from, to);
+            }
+        }
+    }
+
+    /// The receiver is the first argument, and the only one whose taint the built-in identity methods pass on,
+    /// rather than others such as the index passed to `Index::index`.
+    fn t_visit_identity(&mut self, args: &[Operand], destination: &Option<(Place, BasicBlock)>) {
+        match (args.first().and_then(|arg| arg.place()), destination) {
+            (Some(receiver), Some((place, _))) => self.t_propagate(receiver.local, place.local),
+            _ => self.t_visit_sanitizer_destination(destination),
+        }
+    }
+
+    /// Whether `id` is one of the methods of `Try` which `?` desugars to, for any implementation,
+    /// such as those of `Result`, `Option` and `ControlFlow`, or one in the analyzed crate.
+    /// Newer versions of the trait name these `branch` and `FromResidual::from_residual`.
+    /// Like the identity methods, they are only modeled when no `--taint-flow` is given for them.
+    fn t_is_try_conversion(&self, id: &DefId) -> bool {
+        let lang_items = self.tcx.lang_items();
+        [
+            lang_items.into_result_fn(),
+            lang_items.from_error_fn(),
+            lang_items.from_ok_fn(),
+        ]
+        .contains(&Some(*id))
+    }
+
+    /// The values to format are passed by reference, such as `&&Request` for `{:?}` of `&request`,
+    /// which carries the taint of the value, so the result is tainted if any argument is.
+    /// This does not depend on how calls into the formatting machinery without a body, such as `transmute`, are treated.
+    fn t_visit_format(&mut self, args: &[Operand], destination: &Option<(Place, BasicBlock)>) {
+        let tainted = args
+            .iter()
+            .filter_map(|arg| arg.place())
+            .find(|place| self.state.get_taint(place.local));
+
+        match (tainted, destination) {
+            (Some(arg), Some((place, _))) => {
+                self.t_propagate(arg.local, place.local);
+                self.t_weaken(place.local, Confidence::Medium);
+            }
+            _ => self.t_visit_sanitizer_destination(destination),
+        }
+    }
+
+    /// Either the value of the receiver or the default is returned, and the closure of `unwrap_or_else`
+    /// is tainted if it captures tainted values, so the result is tainted if any argument is.
+    /// The closure is called through `FnOnce::call_once`, whose result depends on how calls without a body are treated.
+    fn t_visit_unwrap_or(&mut self, args: &[Operand], destination: &Option<(Place, BasicBlock)>) {
+        let tainted = args
+            .iter()
+            .filter_map(|arg| arg.place())
+            .find(|place| self.state.get_taint(place.local));
+
+        match (tainted, destination) {
+            (Some(arg), Some((place, _))) => self.t_propagate(arg.local, place.local),
+            _ => self.t_visit_sanitizer_destination(destination),
+        }
+    }
+
+    /// Whether `id` is `mem::swap`, `mem::replace` or `mem::take`, which move values through raw pointers
+    /// that their summaries cannot follow.
+    fn t_is_mem_exchange(&self, id: &DefId) -> bool {
+        let path = self.tcx.def_path_str(*id);
+        matches!(
+            path.strip_prefix("std::")
+                .or_else(|| path.strip_prefix("core::")),
+            Some("mem::swap" | "mem::replace" | "mem::take")
+        )
+    }
+
+    /// The first argument is a reference to the destination, and tainting or cleaning it
+    /// does the same to the locals it points to. `replace` and `take` return the old value,
+    /// and install the second argument or a default value; `swap` exchanges the values of both references.
+    fn t_visit_mem_exchange(
+        &mut self,
+        id: &DefId,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+    ) {
+        let target = match args.first().and_then(|arg| arg.place()) {
+            Some(target) => target,
+            None => return self.t_visit_sanitizer_destination(destination),
+        };
+        let other = args.get(1).and_then(|arg| arg.place());
+
+        match &*self.tcx.item_name(*id).as_str() {
+            "swap" => {
+                if let Some(other) = other {
+                    let tainted = self.state.get_taint(target.local);
+                    let origins = self.origins.get(&target.local).cloned();
+                    self.t_propagate(other.local, target.local);
+                    self.state.set_taint(other.local, tainted);
+                    if let Some(origins) = origins {
+                        self.origins.insert(other.local, origins);
+                    }
+                }
+            }
+            _ => {
+                self.t_visit_identity(args, destination);
+                match other {
+                    Some(value) => self.t_propagate(value.local, target.local),
+                    // A constant, or the default value installed by `take`.
+                    None => self.state.set_taint(target.local, false),
+                }
+            }
+        }
+    }
+
+    /// Values too large for registers are returned through a pointer to the caller's memory,
+    /// but MIR always names the place which receives them, so the ABI does not matter here.
+    /// Like an assignment, a clean value returned into a field does not clean the rest of the local.
+    fn t_visit_sanitizer_destination(&mut self, destination: &Option<(Place, BasicBlock)>) {
+        if let Some((place, _)) = destination {
+            if place.projection.is_empty() {
+                self.state.set_taint(place.local, false);
+            }
+        }
+    }
+
+    /// Whether `id` is `unwrap` or `expect` on an `Option` or a `Result`.
+    fn t_is_panicking_unwrap(&self, id: &DefId) -> bool {
+        let name = self.tcx.item_name(*id);
+        if name != sym::unwrap && name != sym::expect {
+            return false;
+        }
+
+        self.tcx
+            .impl_of_method(*id)
+            .and_then(|impl_id| self.tcx.type_of(impl_id).ty_adt_def())
+            .map_or(false, |adt| {
+                self.tcx.is_diagnostic_item(sym::option_type, adt.did)
+                    || self.tcx.is_diagnostic_item(sym::result_type, adt.did)
+            })
+    }
+
+    /// Whether `id` is a method of `Iterator` or `IntoIterator`, or creates an iterator over a slice.
+    fn t_is_iterator_method(&self, id: &DefId) -> bool {
+        if let Some(trait_id) = self.tcx.trait_of_item(*id) {
+            return self.tcx.is_diagnostic_item(sym::Iterator, trait_id)
+                || self.tcx.is_diagnostic_item(sym::IntoIterator, trait_id);
+        }
+
+        let name = self.tcx.item_name(*id);
+        (name == sym::iter || name.as_str() == "iter_mut")
+            && self.tcx.impl_of_method(*id).map_or(false, |impl_id| {
+                matches!(self.tcx.type_of(impl_id).kind(), TyKind::Slice(_))
+            })
+    }
+
+    /// Whether `id` is `write_fmt` of `std::io::Write` or `std::fmt::Write`, which `write!` and `writeln!` call.
+    fn t_is_write_fmt(&self, id: &DefId) -> bool {
+        self.tcx.item_name(*id).as_str() == "write_fmt"
+            && self.tcx.trait_of_item(*id).map_or(false, |trait_id| {
+                matches!(
+                    &*self.tcx.def_path_str(trait_id),
+                    "std::io::Write" | "std::fmt::Write"
+                )
+            })
+    }
+
+    /// The formatted arguments are written to the writer, which is tainted by tainted arguments,
+    /// like a buffer such as a `String`. Writers such as standard output, and those configured
+    /// with `--taint-sink-writer`, are output sinks, and receiving tainted arguments is a violation.
+    fn t_visit_write_fmt(
+        &mut self,
+        name: String,
+        args: &[Operand<'tcx>],
+        destination: &Option<(Place, BasicBlock)>,
+        span: &Span,
+    ) {
+        // Only whether the write succeeded is returned.
+        self.t_visit_sanitizer_destination(destination);
+
+        let (writer, arguments) = match (args.get(0), args.get(1)) {
+            (Some(writer), Some(arguments)) => (writer, arguments),
+            _ => return,
+        };
+
+        let is_sink = self.config.enabled(AnalysisOption::OutputSinks)
+            && !matches!(self.shared.borrow().categories, CategoryFilter::Only(_))
+            && match writer.ty(self.body, self.tcx).peel_refs().kind() {
+                TyKind::Adt(adt, _) => {
+                    let path = self.tcx.def_path_str(adt.did);
+                    OUTPUT_WRITERS.contains(&path.as_str())
+                        || self.config.matches(PathList::SinkWriters, &path)
+                }
+                _ => false,
+            };
+        if is_sink {
+            // `write!` is expanded into the call, which is best shown where the macro is invoked.
+            self.t_visit_sink(name, &args[1..], &span.source_callsite());
+        }
+
+        // The receiver is a reference to the writer, and tainting it taints the writer as well.
+        if let (Some(writer), Some(arguments)) = (writer.place(), arguments.place()) {
+            if self.state.get_taint(arguments.local) {
+                self.t_propagate(arguments.local, writer.local);
+                self.t_weaken(writer.local, Confidence::Medium);
+            }
+        }
+    }
+
+    /// The generator of the async function or block whose future is polled by `id`, if it is `Future::poll`.
+    /// `.await` is lowered into a loop which polls the future, and yields while it is pending.
+    /// The future is an opaque type, which is revealed to the wrapper of the generator.
+    fn t_awaited_generator(&self, id: &DefId, substs: SubstsRef<'tcx>) -> Option<DefId> {
+        let future = self.tcx.lang_items().future_trait()?;
+        if self.tcx.trait_of_item(*id) != Some(future) {
+            return None;
+        }
+
+        let substs = self
+            .tcx
+            .normalize_erasing_regions(ParamEnv::reveal_all(), substs);
+        substs
+            .types()
+            .flat_map(|ty| ty.walk())
+            .find_map(|arg| match arg.unpack() {
+                GenericArgKind::Type(ty) => match *ty.kind() {
+                    TyKind::Generator(generator, _, _) => Some(generator),
+                    _ => None,
+                },
+                _ => None,
+            })
+    }
+
+    /// Polling the future resumes its generator, which holds the arguments of the async function,
+    /// and whose return value is the output of the future, once it is ready.
+    /// It is analyzed with the taint of the future, and the context passed to it is clean.
+    fn t_visit_await(
+        &mut self,
+        generator: &DefId,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+        span: &Span,
+    ) {
+        if !self.tcx.is_mir_available(*generator) {
+            return self.t_visit_identity(args, destination);
+        }
+
+        let future = args
+            .first()
+            .and_then(|arg| arg.place())
+            .map(|place| self.state.get_taint(place.local));
+        let end_state = self.t_function_summary(generator, vec![future, Some(false)]);
+
+        match end_state {
+            Some(end_state) if end_state.get_taint(RETURN_PLACE) => {
+                self.t_visit_source_destination(destination, span);
+                if let Some((place, _)) = destination {
+                    self.t_weaken(place.local, Confidence::Medium);
+                }
+            }
+            Some(_) => self.t_visit_sanitizer_destination(destination),
+            None => self.t_visit_identity(args, destination),
+        }
+    }
+
+    /// The collection whose method `id` is, once resolved with `substs`, if it is one of the modeled methods
+    /// of `HashMap`, `BTreeMap` or `Vec`. Other functions of the same name, such as a free `get`
+    /// which takes a `&HashMap`, are analyzed like any other function. Slice methods such as `get` or `iter`,
+    /// which a `Vec` is dereferenced to, are handled where slices are.
+    fn t_collection_of_method(&self, id: &DefId, substs: SubstsRef<'tcx>) -> Option<DefId> {
+        let method = self.t_resolve(id, substs).unwrap_or(*id);
+        let collection = match self
+            .tcx
+            .impl_of_method(method)
+            .map(|impl_id| self.tcx.type_of(impl_id).peel_refs().kind())
+        {
+            Some(TyKind::Adt(adt, _)) => adt.did,
+            _ => return None,
+        };
+        let name = self.tcx.item_name(method);
+
+        let modeled = if self.tcx.is_diagnostic_item(sym::hashmap_type, collection)
+            || self.tcx.is_diagnostic_item(sym::BTreeMap, collection)
+        {
+            matches!(
+                &*name.as_str(),
+                "insert"
+                    | "get"
+                    | "get_mut"
+                    | "get_key_value"
+                    | "index"
+                    | "remove"
+                    | "remove_entry"
+                    | "iter"
+                    | "iter_mut"
+                    | "keys"
+                    | "values"
+                    | "values_mut"
+                    | "into_iter"
+            )
+        } else if self.tcx.is_diagnostic_item(sym::vec_type, collection) {
+            matches!(
+                &*name.as_str(),
+                "push"
+                    | "insert"
+                    | "extend_from_slice"
+                    | "pop"
+                    | "remove"
+                    | "swap_remove"
+                    | "as_slice"
+                    | "as_mut_slice"
+                    | "into_iter"
+            )
+        } else {
+            false
+        };
+        modeled.then(|| collection)
+    }
+
+    /// Collections are modeled as a whole, like iterators, rather than analyzed through the standard library:
+    /// inserting a tainted key or element taints the collection, and everything read from a tainted collection
+    /// is tainted, whichever key or index it is read with, including the elements it is iterated over with.
+    /// A collection is never cleaned by removing its tainted entries.
+    fn t_visit_collection_call(
+        &mut self,
+        id: &DefId,
+        collection: DefId,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+    ) {
+        // The result, such as the value replaced by `insert`, is read from the collection as it was before the call.
+        self.t_visit_identity(args, destination);
+        if let Some((place, _)) = destination {
+            self.t_weaken(place.local, Confidence::Medium);
+        }
+
+        let name = self.tcx.item_name(*id);
+        if matches!(&*name.as_str(), "insert" | "push" | "extend_from_slice") {
+            // `Vec::insert` takes an index before the element, and only the element is stored.
+            let inserted = if &*name.as_str() == "insert"
+                && self.tcx.is_diagnostic_item(sym::vec_type, collection)
+            {
+                &args[2..]
+            } else {
+                &args[1..]
+            };
+            let tainted = inserted
+                .iter()
+                .filter_map(|arg| arg.place())
+                .find(|place| self.state.get_taint(place.local));
+            // The receiver is a reference to the collection, and tainting it taints the collection as well.
+            if let (Some(collection), Some(from)) = (args[0].place(), tainted) {
+                self.t_propagate(from.local, collection.local);
+                self.t_weaken(collection.local, Confidence::Medium);
+            }
+        }
+    }
+
+    /// Iterators are modeled coarsely rather than analyzed through the standard library:
+    /// the result of an adapter such as `map` or `filter`, of `collect`, or of `next`,
+    /// is tainted if the iterator it is called on, or the closure passed to it, is tainted.
+    /// Closures are built from their captures, so a closure with a tainted capture is tainted.
+    fn t_visit_iterator_call(
+        &mut self,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+    ) {
+        let tainted = args
+            .iter()
+            .filter_map(|arg| arg.place())
+            .find(|place| self.state.get_taint(place.local));
+
+        if let Some((place, _)) = destination {
+            match tainted {
+                Some(from) => {
+                    self.t_propagate(from.local, place.local);
+                    self.t_weaken(place.local, Confidence::Medium);
+                }
+                None => self.t_visit_sanitizer_destination(destination),
+            }
+        }
+    }
+
+    /// Channels cannot be told apart, so a tainted value sent through any `Sender<T>`
+    /// taints the values received from every `Receiver<T>` with the same `T`.
+    fn t_visit_channel(
+        &mut self,
+        id: &DefId,
+        substs: SubstsRef<'tcx>,
+        args: &[Operand],
+        destination: &Option<(Place, BasicBlock)>,
+        span: &Span,
+    ) {
+        let channel = match self
+            .tcx
+            .impl_of_method(*id)
+            .and_then(|impl_id| self.tcx.type_of(impl_id).ty_adt_def())
+        {
+            Some(adt) => self.tcx.def_path_str(adt.did),
+            None => return,
+        };
+        // Lifetimes do not tell channels apart either.
+        let element = match substs.types().next() {
+            Some(ty) => self.tcx.erase_regions(ty),
+            None => return,
+        };
+
+        match (channel.as_str(), &*self.tcx.item_name(*id).as_str()) {
+            ("std::sync::mpsc::Sender" | "std::sync::mpsc::SyncSender", "send" | "try_send") => {
+                // The value is the second argument, after the sender.
+                let tainted = args
+                    .get(1)
+                    .and_then(|arg| arg.place())
+                    .map_or(false, |place| self.state.get_taint(place.local));
+                if tainted {
+                    self.shared.borrow_mut().tainted_channels.insert(element);
+                }
+            }
+            ("std::sync::mpsc::Receiver", "recv" | "try_recv" | "recv_timeout") => {
+                if self.shared.borrow().tainted_channels.contains(&element) {
+                    self.t_visit_source_destination(destination, span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `id` starts a panic with a message, which `panic!` passes formatted as `Arguments`
+    /// or, with a single argument, as is, and `panic_any` passes as is.
+    fn t_is_panic_with_message(&self, id: &DefId) -> bool {
+        let lang_items = self.tcx.lang_items();
+        if lang_items.begin_panic_fn() == Some(*id) || lang_items.panic_str() == Some(*id) {
+            return true;
+        }
+
+        let krate = self.tcx.crate_name(id.krate);
+        (krate == sym::std || krate == sym::core)
+            && matches!(
+                &*self.tcx.item_name(*id).as_str(),
+                "begin_panic_fmt" | "panic_fmt" | "panic_any"
+            )
+    }
+
+    /// The message is written to standard error, or wherever the panic hook sends it,
+    /// so the formatted arguments must not include tainted values.
+    fn t_visit_panic_message(&mut self, name: &str, args: &[Operand], span: &Span) {
+        let tainted_arg = args
+            .iter()
+            .filter_map(|arg| arg.place())
+            .find(|place| self.state.get_taint(place.local));
+
+        if let Some(arg) = tainted_arg {
+            let origin = self.t_origin(arg.local);
+            // `panic!` is expanded into the call, which is best shown where the macro is invoked.
+            let span = span.source_callsite();
+            if !self.t_record_violation(PANIC_MESSAGE_RULE, name, &span, &span, origin) {
+                return;
+            }
+
+            self.t_emit(super::errors::TaintedPanicMessage { span });
+        }
+    }
+
+    /// The receiver is the first argument; the message passed to `expect` is irrelevant.
+    fn t_visit_panicking_unwrap(&mut self, name: String, args: &[Operand], span: &Span) {
+        if let Some(receiver) = args.first().and_then(|arg| arg.place()) {
+            if self.state.get_taint(receiver.local) {
+                self.shared.borrow_mut().tainted_panics += 1;
+                let origin = self.t_origin(receiver.local);
+                if !self.t_record_violation(PANIC_RULE, &name, span, span, origin) {
+                    return;
+                }
+
+                self.t_emit(super::errors::TaintedPanic {
+                    fn_name: name,
+                    span: *span,
+                });
+            }
+        }
     }
 
-    fn t_visit_sink(&mut self, name: String, args: &[Operand], span: &Span) {
-        if args.iter().map(|op| op.place()).any(|el| {
-            if let Some(place) = el {
-                self.state.get_taint(place.local)
+    /// Only the index is checked: a tainted length, such as that of a tainted slice,
+    /// cannot make a clean index go out of bounds by itself.
+    fn t_visit_bounds_check(&mut self, index: &Operand, span: &Span) {
+        if let Some(place) = index.place() {
+            if self.state.get_taint(place.local) {
+                self.shared.borrow_mut().tainted_panics += 1;
+                let origin = self.t_origin(place.local);
+                if !self.t_record_violation(INDEX_RULE, "index", span, span, origin) {
+                    return;
+                }
+
+                self.t_emit(super::errors::TaintedIndex { span: *span });
+            }
+        }
+    }
+
+    /// Returns whether the sink may receive tainted input, or is called depending on it,
+    /// even if the violation is not reported again.
+    fn t_visit_sink(&mut self, name: String, args: &[Operand], span: &Span) -> bool {
+        let tainted_arg = args
+            .iter()
+            .filter_map(|op| op.place())
+            .find(|place| self.state.get_taint(place.local));
+
+        if let Some(arg) = tainted_arg {
+            // Temporaries are spanned by the expression which computes them,
+            // which points at the argument more precisely than the whole call.
+            let decl = &self.body.local_decls[arg.local];
+            let arg_span = if !decl.is_user_variable() && span.contains(decl.source_info.span) {
+                decl.source_info.span
             } else {
-                false
+                *span
+            };
+
+            // Without an origin, the taint did not flow from a source along a known path.
+            let origin = self.t_origin(arg.local);
+            if !self.t_record_violation(SINK_RULE, &name, span, &arg_span, origin) {
+                return true;
             }
-        }) {
-            self.tcx.sess.emit_err(super::errors::TaintedSink {
+
+            // The source info of a call terminator spans the call expression,
+            // rather than the whole statement it is part of.
+            let mir_location = self.config.enabled(AnalysisOption::MirLocations).then(|| {
+                let function = self.tcx.def_path_str(self.body.source.def_id());
+                let span = self.body.source_info(self.location).span;
+                (format!("{:?} in `{}`", self.location, function), span)
+            });
+            self.t_emit(super::errors::TaintedSink {
+                fn_name: name,
+                span: arg_span,
+                origin: origin.map(|(span, _)| span),
+                mir_location,
+            });
+            true
+        } else if self.config.enabled(AnalysisOption::TaintedGuardIsSink)
+            && self.t_in_tainted_branch()
+        {
+            // The arguments are clean, but whether the sink is called at all depends on tainted data.
+            if !self.t_record_violation(GUARD_RULE, &name, span, span, None) {
+                return true;
+            }
+
+            self.t_emit(super::errors::TaintedGuard {
                 fn_name: name,
                 span: *span,
             });
+            true
+        } else {
+            false
+        }
+    }
+
+    fn t_emit(&self, diagnostic: impl SessionDiagnostic<'tcx>) {
+        if !self.shared.borrow().silent {
+            self.tcx.sess.emit_err(diagnostic);
+        }
+    }
+
+    /// Record a violation of `rule` by a call to `sink` at `span`, where `highlight` is the part shown in its snippet,
+    /// and `origin` is where the taint was introduced and how directly it flowed from there.
+    /// Without an origin, the taint did not flow from a source along a known path.
+    /// Returns whether it should be reported, which it is not if the baseline already lists it.
+    fn t_record_violation(
+        &mut self,
+        rule: &str,
+        sink: &str,
+        span: &Span,
+        highlight: &Span,
+        origin: Option<(Span, Confidence)>,
+    ) -> bool {
+        let function = self.tcx.def_path_str(self.body.source.def_id());
+        let location = self.t_resolve_span(span);
+        let fingerprint = self.t_fingerprint(sink);
+        let confidence = origin.map_or(Confidence::Low, |(_, confidence)| confidence);
+
+        // Baselines written before violations had fingerprints list them by location.
+        let mut shared = self.shared.borrow_mut();
+        if shared
+            .baseline
+            .contains(&(function.clone(), sink.to_owned(), fingerprint.clone()))
+            || shared
+                .baseline
+                .contains(&(function.clone(), sink.to_owned(), location.clone()))
+            || (self.config.enabled(AnalysisOption::FailFast) && !shared.violations.is_empty())
+        {
+            return false;
+        }
+        if confidence < self.config.min_confidence() {
+            shared
+                .suppressed
+                .insert((function, sink.to_owned(), location));
+            return false;
+        }
+
+        let mut metadata = BTreeMap::new();
+        if let CategoryFilter::Only(category) = &shared.categories {
+            metadata.insert("category".to_owned(), category.clone());
         }
+
+        shared.violations.insert(SinkViolation {
+            function,
+            sink: sink.to_owned(),
+            rule: rule.to_owned(),
+            location,
+            fingerprint,
+            snippet: self.t_snippet(highlight),
+            confidence,
+            source: origin.map(|(span, _)| self.t_resolve_span(&span)),
+            metadata,
+        });
+        true
+    }
+
+    /// Identifies a violation of `sink` at the current location across analysis runs, without line numbers:
+    /// a hash of the path of the function, the sink, and how many blocks before the current one
+    /// end with a call to the same function, or with a terminator of the same kind if the current one is not a call.
+    fn t_fingerprint(&self, sink: &str) -> String {
+        let kind = |data: &BasicBlockData<'tcx>| {
+            let kind = &data.terminator().kind;
+            let callee = match kind {
+                TerminatorKind::Call { func, .. } => match func.ty(self.body, self.tcx).kind() {
+                    TyKind::FnDef(id, _) => Some(*id),
+                    _ => None,
+                },
+                _ => None,
+            };
+            (std::mem::discriminant(kind), callee)
+        };
+        let blocks = self.body.basic_blocks();
+        let current = kind(&blocks[self.location.block]);
+        let ordinal = blocks
+            .iter()
+            .take(self.location.block.index())
+            .filter(|data| kind(data) == current)
+            .count();
+
+        let mut hasher = StableHasher::new();
+        self.tcx
+            .def_path_hash(self.body.source.def_id())
+            .0
+            .hash(&mut hasher);
+        sink.hash(&mut hasher);
+        ordinal.hash(&mut hasher);
+        let fingerprint: Fingerprint = hasher.finish();
+        fingerprint.to_hex()
+    }
+
+    /// Record a MIR construct which does not affect the taint of any local,
+    /// even though it might, to find gaps in the analysis.
+    fn t_unhandled(&mut self, construct: &'static str) {
+        if self.config.enabled(AnalysisOption::ReportUnhandled) {
+            let function = self.tcx.def_path_str(self.body.source.def_id());
+            let location = format!("{:?}", self.location);
+            self.shared
+                .borrow_mut()
+                .unhandled
+                .entry(construct)
+                .or_default()
+                .insert((function, location));
+        }
+    }
+
+    /// Resolve a span to a `file:line:column` string.
+    fn t_resolve_span(&self, span: &Span) -> String {
+        let loc = self.tcx.sess.source_map().lookup_char_pos(span.lo());
+        format!("{}:{}:{}", loc.file.name, loc.line, loc.col_display + 1)
+    }
+
+    /// The lines around the start of `span`, with carets under the part of `span` on its first line.
+    fn t_snippet(&self, span: &Span) -> String {
+        const CONTEXT: usize = 1;
+
+        let source_map = self.tcx.sess.source_map();
+        let lo = source_map.lookup_char_pos(span.lo());
+        let hi = source_map.lookup_char_pos(span.hi());
+        let file = &lo.file;
+
+        // Lines are numbered from 1, but indexed from 0.
+        let first = lo.line.saturating_sub(CONTEXT).max(1);
+        let last = (lo.line + CONTEXT).min(file.count_lines());
+        let width = last.to_string().len();
+
+        let mut snippet = String::new();
+        for line in first..=last {
+            let text = match file.get_line(line - 1) {
+                Some(text) => text,
+                None => continue,
+            };
+            snippet.push_str(&format!("{:>width$} | {}\n", line, text, width = width));
+
+            if line == lo.line {
+                let end = if hi.line == lo.line {
+                    hi.col_display
+                } else {
+                    text.chars().count()
+                };
+                let carets = "^".repeat(end.saturating_sub(lo.col_display).max(1));
+                snippet.push_str(&format!(
+                    "{:>width$} | {:indent$}{}\n",
+                    "",
+                    "",
+                    carets,
+                    width = width,
+                    indent = lo.col_display
+                ));
+            }
+        }
+        snippet
+    }
+}
+
+/// The blocks which may be reached from the start of `body`, skipping the branches of a `SwitchInt`
+/// on a constant, or on a local assigned a constant in the same block, which are never taken.
+/// Optimization usually removes such branches already, but the analysis must not rely on it.
+fn reachable_blocks<'tcx>(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) -> BitSet<BasicBlock> {
+    let param_env = tcx.param_env(body.source.def_id());
+    let mut reachable = BitSet::new_empty(body.basic_blocks().len());
+    let mut worklist = vec![START_BLOCK];
+
+    while let Some(block) = worklist.pop() {
+        if !reachable.insert(block) {
+            continue;
+        }
+
+        let data = &body[block];
+        let terminator = data.terminator();
+        let value = match &terminator.kind {
+            TerminatorKind::SwitchInt {
+                discr, switch_ty, ..
+            } => constant_discr(data, discr)
+                .and_then(|constant| constant.literal.try_eval_bits(tcx, param_env, switch_ty)),
+            _ => None,
+        };
+
+        match (&terminator.kind, value) {
+            (TerminatorKind::SwitchInt { targets, .. }, Some(value)) => {
+                let target = targets
+                    .iter()
+                    .find(|(v, _)| *v == value)
+                    .map_or_else(|| targets.otherwise(), |(_, target)| target);
+                worklist.push(target);
+            }
+            _ => worklist.extend(terminator.successors().copied()),
+        }
+    }
+
+    reachable
+}
+
+/// The constant which `discr` is known to be at the end of `data`.
+fn constant_discr<'a, 'tcx>(
+    data: &'a BasicBlockData<'tcx>,
+    discr: &'a Operand<'tcx>,
+) -> Option<&'a Constant<'tcx>> {
+    let place = match discr {
+        Operand::Constant(constant) => return Some(constant),
+        Operand::Copy(place) | Operand::Move(place) => place,
+    };
+    if !place.projection.is_empty() {
+        return None;
+    }
+
+    // Only the last assignment to the local in the block matters.
+    let rvalue = data
+        .statements
+        .iter()
+        .rev()
+        .find_map(|statement| match &statement.kind {
+            StatementKind::Assign(box (assigned, rvalue)) if assigned.local == place.local => {
+                Some((assigned, rvalue))
+            }
+            _ => None,
+        });
+    match rvalue {
+        Some((assigned, Rvalue::Use(Operand::Constant(constant))))
+            if assigned.projection.is_empty() =>
+        {
+            Some(constant)
+        }
+        _ => None,
+    }
+}
+
+/// Whether `local`, which `data` switches on, is the result of a comparison or a discriminant computed in `data`.
+fn is_test(data: &BasicBlockData<'_>, local: Local) -> bool {
+    data.statements
+        .iter()
+        .rev()
+        .find_map(|statement| match &statement.kind {
+            StatementKind::Assign(box (assigned, rvalue)) if assigned.local == local => {
+                Some(rvalue)
+            }
+            _ => None,
+        })
+        .map_or(false, |rvalue| match rvalue {
+            Rvalue::Discriminant(_) => true,
+            Rvalue::BinaryOp(op, _) | Rvalue::CheckedBinaryOp(op, _) => is_comparison(*op),
+            _ => false,
+        })
+}
+
+/// The blocks which assign the result of a test which branches to `targets`, and the local they assign it to,
+/// if each branch, or a block it goes to, assigns a boolean constant to the same local, as `matches!` does.
+/// Branches which only lead to other code, such as a call with a constant argument, are not the result of the test.
+fn test_results(body: &Body<'_>, targets: &[BasicBlock]) -> Vec<(BasicBlock, Local)> {
+    let assigned = |mut block: BasicBlock| {
+        // Following the gotos from one block to the next, without going around a loop.
+        for _ in 0..body.basic_blocks().len() {
+            let data = &body[block];
+            let constant = data
+                .statements
+                .iter()
+                .find_map(|statement| match &statement.kind {
+                    StatementKind::Assign(box (place, Rvalue::Use(Operand::Constant(c))))
+                        if place.projection.is_empty() && c.ty().is_bool() =>
+                    {
+                        Some(place.local)
+                    }
+                    _ => None,
+                });
+            match (constant, &data.terminator().kind) {
+                (Some(local), _) => return Some((block, local)),
+                (None, TerminatorKind::Goto { target }) => block = *target,
+                _ => return None,
+            }
+        }
+        None
+    };
+
+    let results = targets
+        .iter()
+        .filter(|&&target| !matches!(body[target].terminator().kind, TerminatorKind::Unreachable))
+        .map(|&target| assigned(target))
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default();
+    match results.first() {
+        Some((_, local)) if results.iter().all(|(_, other)| other == local) => results,
+        _ => Vec::new(),
+    }
+}
+
+fn is_pointer_int_cast(source: Ty<'_>, target: Ty<'_>) -> bool {
+    let is_pointer = |ty: Ty<'_>| ty.is_unsafe_ptr() || ty.is_fn_ptr();
+
+    (is_pointer(source) && target.is_integral()) || (source.is_integral() && is_pointer(target))
+}
+
+fn is_comparison(op: BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge
+    )
+}
+
+/// The locals of `body` which its summary describes to callers: the return value and the arguments.
+fn summary_locals(body: &Body<'_>) -> impl Iterator<Item = Local> {
+    std::iter::once(RETURN_PLACE).chain(body.args_iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{find_fn, with_tcx, CoreConfig};
+
+    #[test]
+    fn binary_op_clears_moved_operands() {
+        let program = r#"
+fn main() {}
+
+fn mask(a: i32, b: i32) -> i32 {
+    (a & 0xff) | b
+}
+"#;
+
+        with_tcx(program, |tcx| {
+            let body = tcx.optimized_mir(find_fn(tcx, "mask"));
+            let info = AttrInfo::default();
+            let config = CoreConfig::default();
+            let init = vec![Some(true), Some(false)];
+            let state =
+                TaintAnalysis::new_with_init(tcx, &info, &config, body, Rc::default(), init)
+                    .into_exit_state()
+                    .unwrap();
+
+            // The result is tainted by `a`.
+            assert!(state.contains(Local::from_usize(0)));
+
+            // `a & 0xff` is moved into the bitwise or, and is clean afterwards.
+            let moved = body
+                .basic_blocks()
+                .iter()
+                .flat_map(|data| data.statements.iter())
+                .find_map(|statement| match &statement.kind {
+                    StatementKind::Assign(box (
+                        _,
+                        Rvalue::BinaryOp(_, box (Operand::Move(p), _)),
+                    )) => Some(p.local),
+                    _ => None,
+                })
+                .unwrap();
+            assert!(!state.contains(moved));
+        });
+    }
+
+    #[test]
+    fn loop_converges_monotonically() {
+        let program = r#"
+fn main() {}
+
+fn shift(a: i32, n: i32) -> i32 {
+    let (mut x, mut y, mut z) = (0, 0, 0);
+    let mut i = 0;
+    while i < n {
+        z = y;
+        y = x;
+        x = a;
+        i += 1;
+    }
+    z
+}
+"#;
+
+        with_tcx(program, |tcx| {
+            let body = tcx.optimized_mir(find_fn(tcx, "shift"));
+            let info = AttrInfo::default();
+            let config = CoreConfig::default();
+            let init = vec![Some(true), Some(false)];
+            let (_, trace) =
+                TaintAnalysis::new_with_init(tcx, &info, &config, body, Rc::default(), init)
+                    .trace_convergence()
+                    .into_exit_state_and_trace();
+            let trace = trace.unwrap();
+
+            // The taint of `a` reaches `z` after several iterations of the loop.
+            assert!(trace.iterations() > 1);
+            assert!(trace
+                .blocks
+                .values()
+                .any(|sizes| sizes.first() < sizes.last()));
+            assert!(trace.non_monotone_blocks().is_empty());
+
+            // With a tainted bound, the iteration starts over once the loop turns out to branch on it,
+            // which the trace must not take for the sets shrinking.
+            let config = CoreConfig(vec![AnalysisOption::ImplicitFlows]);
+            let init = vec![Some(true), Some(true)];
+            let (_, trace) =
+                TaintAnalysis::new_with_init(tcx, &info, &config, body, Rc::default(), init)
+                    .trace_convergence()
+                    .into_exit_state_and_trace();
+            assert!(trace.unwrap().non_monotone_blocks().is_empty());
+        });
+    }
+
+    #[test]
+    fn constant_branch_is_pruned() {
+        let program = r#"
+fn main() {}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+fn debug_only() -> i32 {
+    let mut x = 0;
+    if false {
+        x = input();
+    }
+    x
+}
+"#;
+
+        with_tcx(program, |tcx| {
+            // Optimized MIR has the dead branch removed already, so analyze the MIR before optimizations.
+            let id = find_fn(tcx, "debug_only").expect_local();
+            let body = tcx
+                .mir_promoted(rustc_middle::ty::WithOptConstParam::unknown(id))
+                .0
+                .borrow()
+                .clone();
+            let body = tcx.arena.alloc(body);
+
+            let info = AttrInfo {
+                sources: vec![find_fn(tcx, "input")],
+                ..AttrInfo::default()
+            };
+            let config = CoreConfig::default();
+            let state = TaintAnalysis::new(tcx, &info, &config, body)
+                .into_exit_state()
+                .unwrap();
+
+            assert!(!state.contains(RETURN_PLACE));
+        });
+    }
+
+    #[test]
+    fn drop_cleanup_sees_state_before_drop() {
+        let program = r#"
+fn main() {}
+
+fn both(_a: Vec<i32>, _b: Vec<i32>) {}
+"#;
+
+        with_tcx(program, |tcx| {
+            let body = tcx.optimized_mir(find_fn(tcx, "both"));
+            let info = AttrInfo::default();
+            let config = CoreConfig::default();
+            let init = vec![Some(true), Some(false)];
+            let mut results =
+                TaintAnalysis::new_with_init(tcx, &info, &config, body, Rc::default(), init)
+                    .into_results()
+                    .into_results_cursor(body);
+
+            let (block, cleanup) = body
+                .basic_blocks()
+                .iter_enumerated()
+                .find_map(|(block, data)| match data.terminator().kind {
+                    TerminatorKind::Drop {
+                        unwind: Some(cleanup),
+                        ..
+                    } => Some((block, cleanup)),
+                    _ => None,
+                })
+                .unwrap();
+
+            results.seek_before_primary_effect(body.terminator_loc(block));
+            let before = results.get().clone();
+            assert!(before.tainted.contains(Local::from_usize(1)));
+
+            results.seek_to_block_start(cleanup);
+            assert_eq!(results.get(), &before);
+        });
+    }
+
+    #[test]
+    fn config_is_read_through_the_trait() {
+        /// Makes `read` a source, as a tool which uses the core alone may configure it.
+        struct ReadIsSource(SourceSpec);
+
+        impl AnalysisConfig for ReadIsSource {
+            fn source(&self, path: &str) -> Option<&SourceSpec> {
+                (path == self.0.path).then(|| &self.0)
+            }
+        }
+
+        let program = r#"
+fn main() {}
+
+fn read() -> i32 {
+    1
+}
+
+fn copy() -> i32 {
+    let value = read();
+    value
+}
+"#;
+
+        with_tcx(program, |tcx| {
+            let body = tcx.optimized_mir(find_fn(tcx, "copy"));
+            let info = AttrInfo::default();
+            let returns_tainted = |config: &dyn AnalysisConfig| {
+                TaintAnalysis::new(tcx, &info, config, body)
+                    .into_exit_state()
+                    .unwrap()
+                    .contains(RETURN_PLACE)
+            };
+
+            assert!(!returns_tainted(&CoreConfig::default()));
+            let config = ReadIsSource(SourceSpec::parse("read").unwrap());
+            assert!(returns_tainted(&config));
+        });
     }
 }
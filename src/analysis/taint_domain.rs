@@ -1,12 +1,56 @@
 //! A trait to constrain the domain operations to taint analysis.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use rustc_index::{bit_set::BitSet, vec::Idx};
 use rustc_middle::mir::{Local, Place};
-use tracing::instrument;
+use rustc_mir::dataflow::{fmt::DebugWithContext, JoinSemiLattice};
+use rustc_span::Span;
+use tracing::{instrument, trace};
 
 use crate::taint_analysis::PointsMap;
+use crate::violation::Confidence;
+
+/// Where the taint of each local may have been introduced, and how directly it may have flowed from there.
+pub(crate) type Origins = BTreeMap<Local, BTreeSet<(Confidence, Span)>>;
+
+/// The state of the analysis at a location: the locals which may be tainted, and the origins of their taint
+/// along every path which reaches the location. Both are joined by union, so the origin a violation is reported with,
+/// and its confidence, are those of the most direct path to it, whichever order the paths are visited in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaintState {
+    pub tainted: BitSet<Local>,
+    pub(crate) origins: Origins,
+}
+
+impl TaintState {
+    pub(crate) fn new_empty(locals: usize) -> Self {
+        TaintState {
+            tainted: BitSet::new_empty(locals),
+            origins: Origins::new(),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.tainted.clear();
+        self.origins.clear();
+    }
+}
+
+impl JoinSemiLattice for TaintState {
+    fn join(&mut self, other: &Self) -> bool {
+        let mut changed = self.tainted.join(&other.tainted);
+        for (local, origins) in other.origins.iter() {
+            let joined = self.origins.entry(*local).or_default();
+            for origin in origins {
+                changed |= joined.insert(*origin);
+            }
+        }
+        changed
+    }
+}
+
+impl<C> DebugWithContext<C> for TaintState {}
 
 #[derive(Debug)]
 pub(crate) struct PointsAwareTaintDomain<'a, T: Idx> {
@@ -43,6 +87,7 @@ impl<T: Idx> TaintDomain<T> for BitSet<T> {
 
 impl TaintDomain<Local> for PointsAwareTaintDomain<'_, Local> {
     fn propagate(&mut self, old: Local, new: Local) {
+        trace!("propagate {:?} -> {:?}", old, new);
         self.set_taint(new, self.get_taint(old));
     }
 
@@ -51,6 +96,7 @@ impl TaintDomain<Local> for PointsAwareTaintDomain<'_, Local> {
     }
 
     fn set_taint(&mut self, ix: Local, value: bool) {
+        trace!("{} {:?}", if value { "gen" } else { "kill" }, ix);
         let children = self.get_aliases(ix);
 
         for child in children {
@@ -96,7 +142,13 @@ impl PointsAwareTaintDomain<'_, Local> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
     use rustc_middle::mir::Local;
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
 
     use super::*;
 
@@ -127,4 +179,93 @@ mod tests {
         // One should not be tainted.
         assert!(!set.get_taint(ONE));
     }
+
+    #[test]
+    fn propagate_to_self() {
+        let mut state: BitSet<Local> = BitSet::new_empty(4);
+        let mut map = PointsMap::new();
+        let mut domain = PointsAwareTaintDomain {
+            state: &mut state,
+            map: &mut map,
+        };
+
+        // `x = x` reads the taint of `x` before writing it, so it keeps its taint either way.
+        domain.set_taint(ONE, true);
+        domain.propagate(ONE, ONE);
+        domain.propagate(TWO, TWO);
+        assert!(domain.get_taint(ONE));
+        assert!(!domain.get_taint(TWO));
+
+        let mut set: BitSet<Local> = BitSet::new_empty(4);
+        set.set_taint(ONE, true);
+        set.propagate(ONE, ONE);
+        set.propagate(TWO, TWO);
+        assert!(set.get_taint(ONE));
+        assert!(!set.get_taint(TWO));
+    }
+
+    #[test]
+    fn trace_propagation() {
+        let events = EventCollector::default();
+        let mut state: BitSet<Local> = BitSet::new_empty(4);
+        let mut map = PointsMap::new();
+
+        tracing::subscriber::with_default(events.clone(), || {
+            let mut domain = PointsAwareTaintDomain {
+                state: &mut state,
+                map: &mut map,
+            };
+
+            domain.set_taint(ONE, true);
+            domain.propagate(ONE, TWO);
+            domain.propagate(THREE, ONE);
+        });
+
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            vec![
+                "gen _1",
+                "propagate _1 -> _2",
+                "gen _2",
+                "propagate _3 -> _1",
+                "kill _1",
+            ]
+        );
+    }
+
+    /// Collects the messages of all events emitted while it is the default subscriber.
+    #[derive(Default, Clone)]
+    struct EventCollector(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for EventCollector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut MessageVisitor(&mut self.0.lock().unwrap()));
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    struct MessageVisitor<'a>(&'a mut Vec<String>);
+
+    impl Visit for MessageVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0.push(format!("{:?}", value));
+            }
+        }
+    }
 }
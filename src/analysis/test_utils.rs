@@ -0,0 +1,115 @@
+//! Helpers for unit tests which analyze a whole program.
+//!
+//! Programs are given as source strings, which may use the `taint` attributes directly.
+//! Those which run the analysis over the whole crate need the `eval` feature, the others only the dataflow core.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+
+use rustc_feature::UnstableFeatures;
+use rustc_hir::def_id::DefId;
+#[cfg(feature = "eval")]
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_interface::interface;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::{Input, Options};
+use rustc_session::DiagnosticOutput;
+
+use crate::analysis_config::{AnalysisConfig, AnalysisOption};
+#[cfg(feature = "eval")]
+use crate::eval::{config::TaintConfig, main::eval_main};
+#[cfg(feature = "eval")]
+use crate::report::CrateReport;
+#[cfg(feature = "eval")]
+use crate::violation::SinkViolation;
+
+/// Kept on a single line, so that programs starting with a newline keep their line numbers.
+const PRELUDE: &str = "#![feature(register_tool)] #![register_tool(taint)]";
+
+/// The `input` source and `output` sink which most test programs end with.
+#[cfg(feature = "eval")]
+pub(crate) const INPUT_OUTPUT: &str = r#"
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
+"#;
+
+/// Compile `program` in-process, and analyze it with the default configuration.
+#[cfg(feature = "eval")]
+pub(crate) fn violations(program: &str) -> Vec<SinkViolation> {
+    analyze(program, TaintConfig::default())
+        .violations
+        .into_iter()
+        .collect()
+}
+
+/// Compile `program` in-process, and analyze it with `config`.
+/// Diagnostics are discarded, so the results are only available through the report.
+#[cfg(feature = "eval")]
+pub(crate) fn analyze(program: &str, config: TaintConfig) -> CrateReport {
+    with_tcx(program, |tcx| {
+        let (main_id, _) = tcx.entry_fn(LOCAL_CRATE).unwrap();
+        eval_main(tcx, main_id.to_def_id(), &config)
+    })
+}
+
+/// The configuration of tests of the dataflow core alone, in which only the given options are enabled.
+#[derive(Default)]
+pub(crate) struct CoreConfig(pub(crate) Vec<AnalysisOption>);
+
+impl AnalysisConfig for CoreConfig {
+    fn enabled(&self, option: AnalysisOption) -> bool {
+        self.0.contains(&option)
+    }
+}
+
+/// The local function at `path`.
+pub(crate) fn find_fn(tcx: TyCtxt<'_>, path: &str) -> DefId {
+    tcx.body_owners()
+        .map(|id| id.to_def_id())
+        .find(|id| tcx.def_path_str(*id) == path)
+        .unwrap_or_else(|| panic!("no function `{}`", path))
+}
+
+/// Compile `program` in-process, and call `f` with its type context.
+pub(crate) fn with_tcx<R: Send>(program: &str, f: impl FnOnce(TyCtxt<'_>) -> R + Send) -> R {
+    let sysroot = Command::new("rustc")
+        .args(&["--print", "sysroot"])
+        .output()
+        .expect("failed to find the sysroot");
+    let sysroot = String::from_utf8(sysroot.stdout).unwrap();
+
+    let compiler_config = interface::Config {
+        opts: Options {
+            maybe_sysroot: Some(PathBuf::from(sysroot.trim())),
+            unstable_features: UnstableFeatures::from_environment(None),
+            ..Options::default()
+        },
+        crate_cfg: Default::default(),
+        input: Input::Str {
+            name: rustc_span::FileName::Custom("program.rs".to_owned()),
+            input: format!("{}{}", PRELUDE, program),
+        },
+        input_path: None,
+        output_dir: None,
+        output_file: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Raw(Box::new(io::sink())),
+        stderr: None,
+        lint_caps: Default::default(),
+        parse_sess_created: None,
+        register_lints: None,
+        override_queries: None,
+        make_codegen_backend: None,
+        registry: rustc_errors::registry::Registry::new(&[]),
+    };
+
+    interface::run_compiler(compiler_config, |compiler| {
+        compiler.enter(|queries| queries.global_ctxt().unwrap().peek_mut().enter(f))
+    })
+}
@@ -0,0 +1,145 @@
+//! Violations which the analysis records, and how certain they are.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A call to a sink which may receive tainted input.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SinkViolation {
+    /// The function which calls the sink.
+    pub function: String,
+    /// The sink which is called, or for other rules what may panic, such as `index` for a bounds check.
+    pub sink: String,
+    /// The code of the diagnostic the violation is reported with, such as `T0001` for a sink which received tainted input.
+    pub rule: String,
+    /// Where the sink is called, as `file:line:column`.
+    pub location: String,
+    /// Identifies the violation across analysis runs, even if the code around it moves,
+    /// since it does not depend on line numbers. Empty if it is not known, such as in older reports.
+    pub fingerprint: String,
+    /// The source lines around the call, with carets under the tainted argument.
+    pub snippet: String,
+    /// How likely the violation is to be real, from how the tainted value reached the sink.
+    pub confidence: Confidence,
+    /// Where the tainted value was introduced, such as by a call to a source, if it is known.
+    pub source: Option<String>,
+    /// Additional information attached after the analysis, such as a CWE identifier.
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// The rule of a sink which received tainted input.
+pub const SINK_RULE: &str = "T0001";
+/// The rule of an `unwrap` or `expect` on a tainted value.
+pub const PANIC_RULE: &str = "T0004";
+/// The rule of a sink called in a branch taken on tainted data.
+pub const GUARD_RULE: &str = "T0005";
+/// The rule of indexing with a tainted index.
+pub const INDEX_RULE: &str = "T0006";
+/// The rule of a panic whose message includes a tainted value.
+pub const PANIC_MESSAGE_RULE: &str = "T0007";
+
+/// The shape of the path from a source to a sink, from the least to the most certain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+    /// The path is not known, such as through a function without a body or an implicit flow.
+    Low,
+    /// The value was transformed on its way, such as by arithmetic or a function call,
+    /// which may have sanitized it.
+    Medium,
+    /// The value of the source reached the sink unchanged, through copies, moves and references.
+    High,
+}
+
+impl Confidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        }
+    }
+
+    /// The confidence named `name`, as returned by `as_str`, in any case.
+    pub fn parse(name: &str) -> Option<Self> {
+        match &*name.to_ascii_lowercase() {
+            "low" => Some(Confidence::Low),
+            "medium" => Some(Confidence::Medium),
+            "high" => Some(Confidence::High),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Confidence {
+    fn default() -> Self {
+        Confidence::Low
+    }
+}
+
+/// Whether an annotated sink may receive tainted input anywhere in the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SinkStatus {
+    /// The sink is never called from the analyzed code, so the configuration may be outdated.
+    Unreached,
+    /// The sink is called, but never with tainted input.
+    Clean,
+    /// The sink may receive tainted input, even if the violation is in the baseline.
+    Violated,
+}
+
+impl SinkStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SinkStatus::Unreached => "unreached",
+            SinkStatus::Clean => "clean",
+            SinkStatus::Violated => "violated",
+        }
+    }
+}
+
+impl SinkViolation {
+    /// Identifies the same violation across analysis runs, by its fingerprint,
+    /// or by its location if it has none.
+    pub fn key(&self) -> (&str, &str, &str) {
+        let id = if self.fingerprint.is_empty() {
+            &self.location
+        } else {
+            &self.fingerprint
+        };
+        (&self.function, &self.sink, id)
+    }
+
+    /// Join `other`, the same violation found again, such as in the round of another category of the sink,
+    /// into the one of the more certain flow, which lists the categories of both.
+    pub fn join(self, other: SinkViolation) -> SinkViolation {
+        let (mut kept, other) = if other.confidence > self.confidence {
+            (other, self)
+        } else {
+            (self, other)
+        };
+        let categories = [&kept, &other]
+            .iter()
+            .filter_map(|violation| violation.metadata.get("category"))
+            .flat_map(|categories| categories.split(','))
+            .map(str::to_owned)
+            .collect::<BTreeSet<_>>();
+        if !categories.is_empty() {
+            let categories = categories.into_iter().collect::<Vec<_>>().join(",");
+            kept.metadata.insert("category".to_owned(), categories);
+        }
+        kept
+    }
+
+    /// The message of the diagnostic the violation is reported with.
+    pub fn message(&self) -> String {
+        match &*self.rule {
+            PANIC_RULE => format!("`{}` may panic on tainted input", self.sink),
+            GUARD_RULE => format!(
+                "function `{}` is called depending on tainted input",
+                self.sink
+            ),
+            INDEX_RULE => "tainted index used in bounds-checked access".to_owned(),
+            PANIC_MESSAGE_RULE => "tainted value formatted into a panic message".to_owned(),
+            _ => format!("function `{}` received tainted input", self.sink),
+        }
+    }
+}
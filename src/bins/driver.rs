@@ -16,6 +16,8 @@ use rustc_hir as hir;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::ErrorOutputType;
 use taint::eval;
+use taint::eval::config::TaintConfig;
+use taint::reporter::reporters;
 use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
 
 fn main() {
@@ -29,7 +31,13 @@ fn main() {
         rustc_args.push(arg);
     }
 
-    run_compiler(rustc_args, &mut TaintCompilerCallbacks)
+    let config = TaintConfig::from_args(&mut rustc_args);
+    if config.dry_run {
+        println!("{:#?}", config);
+        return;
+    }
+
+    run_compiler(rustc_args, &mut TaintCompilerCallbacks { config })
 }
 
 /// We want our own tracing to debug the taint analysis.
@@ -41,6 +49,9 @@ fn main() {
 /// It logs when functions marked with `#[instrument]` are entered,
 /// and does not require any further code (such as the `event!` macro
 /// provided by `tracing`).
+///
+/// At the `DEBUG` level, calls are logged along with the location of each statement and terminator.
+/// At the `TRACE` level, every local that is tainted (gen) or cleaned (kill) is logged as well.
 fn init_tracing() {
     if let Ok(filter) = EnvFilter::try_from_env("TAINT_LOG") {
         tracing_subscriber::fmt()
@@ -83,7 +94,9 @@ fn compile_time_sysroot() -> Option<String> {
 
 /// Runs taint analysis once built-in analyses are complete.
 /// No artifacts are emitted, since this is meant to be an analysis tool only.
-struct TaintCompilerCallbacks;
+struct TaintCompilerCallbacks {
+    config: TaintConfig,
+}
 
 impl rustc_driver::Callbacks for TaintCompilerCallbacks {
     /// All the work we do happens after analysis, so that we can make assumptions about the validity of the MIR.
@@ -93,7 +106,7 @@ impl rustc_driver::Callbacks for TaintCompilerCallbacks {
         queries: &'tcx rustc_interface::Queries<'tcx>,
     ) -> Compilation {
         compiler.session().abort_if_errors();
-        enter_with_fn(queries, mir_analysis);
+        enter_with_fn(queries, |tcx| mir_analysis(tcx, &self.config));
         compiler.session().abort_if_errors();
         Compilation::Stop
     }
@@ -108,7 +121,7 @@ where
 }
 
 /// Perform the taint analysis.
-fn mir_analysis(tcx: TyCtxt) {
+fn mir_analysis(tcx: TyCtxt, config: &TaintConfig) {
     let (entry_def_id, _) = if let Some((entry_def, x)) = tcx.entry_fn(LOCAL_CRATE) {
         (entry_def, x)
     } else {
@@ -118,5 +131,51 @@ fn mir_analysis(tcx: TyCtxt) {
     };
 
     let main_id = entry_def_id.to_def_id();
-    main::eval_main(tcx, main_id);
+    let report = main::eval_main(tcx, main_id, config);
+
+    for reporter in reporters(config) {
+        if let Err(err) = reporter.report(&report) {
+            tcx.sess
+                .err(&format!("failed to write taint report to {}", err));
+        }
+    }
+
+    if let Some(path) = &config.results {
+        if let Err(err) = std::fs::write(path, report.to_results()) {
+            tcx.sess.err(&format!(
+                "failed to write taint results to `{}`: {}",
+                path, err
+            ));
+        }
+    }
+
+    if config.function.is_some() {
+        print!("{}", report.to_states());
+    }
+
+    if let Some(path) = &config.call_graph {
+        if let Err(err) = std::fs::write(path, report.call_graph().to_dot()) {
+            tcx.sess.err(&format!(
+                "failed to write the call graph to `{}`: {}",
+                path, err
+            ));
+        }
+    }
+
+    if let (true, Some(api)) = (config.api_table, &report.api) {
+        print!("{}", api.to_table());
+    }
+
+    if let Some(max_taint) = &report.max_taint {
+        print!("{}", max_taint.to_text());
+    }
+
+    if let (Some(path), Some(api)) = (&config.export_summary, &report.api) {
+        if let Err(err) = std::fs::write(path, api.to_json()) {
+            tcx.sess.err(&format!(
+                "failed to write taint summary to `{}`: {}",
+                path, err
+            ));
+        }
+    }
 }
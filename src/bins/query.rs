@@ -0,0 +1,43 @@
+//! An interactive prompt for querying the results of the taint analysis,
+//! as written by the `taint` driver with `--taint-results=<path>`.
+//!
+//! Example: `taint-query results.txt`, then `help` to list the queries.
+
+use std::io::{self, BufRead, Write};
+
+use taint::query;
+use taint::report::CrateReport;
+
+fn main() {
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: taint-query <results>");
+            std::process::exit(2);
+        }
+    };
+    let results = match std::fs::read_to_string(&path) {
+        Ok(text) => CrateReport::from_results(&text),
+        Err(err) => {
+            eprintln!("failed to read taint results from `{}`: {}", path, err);
+            std::process::exit(1);
+        }
+    };
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        match query::answer(&results, &line) {
+            Some(answer) if answer.is_empty() => {}
+            Some(answer) => println!("{}", answer),
+            None => break,
+        }
+    }
+}
@@ -0,0 +1,751 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use rustc_session::config::ErrorOutputType;
+
+use crate::analysis_config::{
+    glob_match, AnalysisConfig, AnalysisOption, FlowSpec, PathList, RecursionAssumption,
+    SourceSpec, UnknownCallPolicy,
+};
+use crate::eval::config_file::ConfigFile;
+use crate::reporter::Reporter;
+use crate::rvalue_model::RvalueModel;
+use crate::violation::Confidence;
+
+/// Options which enable additional checks in the taint analysis.
+///
+/// They are passed to the driver as `--taint-<option>` flags,
+/// which are removed from the arguments before they are handed to `rustc`,
+/// or set in `taint.toml` files in the directory of the crate and its parents.
+#[derive(Default, Debug, Clone)]
+pub struct TaintConfig {
+    /// Warn about public functions which may return a tainted value.
+    pub return_escape: bool,
+    /// Report calls to `unwrap` or `expect` on a tainted `Option` or `Result`,
+    /// since attacker-controlled data should not be able to cause a panic.
+    pub panic_on_tainted_is_sink: bool,
+    /// Report indexing with a tainted index, since it may be out of bounds.
+    pub tainted_index_is_sink: bool,
+    /// Report panics whose message includes a tainted value, since it may be logged or shown to a user.
+    pub panic_message_is_sink: bool,
+    /// Treat the functions which printing and logging macros such as `println!` and `log::info!` call
+    /// as sinks, since their output may be read by others.
+    pub output_sinks: bool,
+    /// Paths of writer types, such as sockets, which `write!` to is an output sink, with `output_sinks`.
+    pub sink_writers: Vec<String>,
+    /// Consider the results of comparisons clean, even if their operands are tainted.
+    pub clean_comparisons: bool,
+    /// Track implicit flows, where a value depends on a branch taken on tainted data.
+    pub implicit_flows: bool,
+    /// Report sinks which are called in a branch taken on tainted data,
+    /// even if their arguments are clean. Branches are tracked for it even without `implicit_flows`.
+    pub tainted_guard_is_sink: bool,
+    /// Emit a note when a tainted value is cast between a pointer and an integer.
+    pub note_pointer_casts: bool,
+    /// Attach the MIR location of each call to a sink to its diagnostic, spanning the call expression.
+    pub mir_locations: bool,
+    /// Taint values received from channels which tainted values were sent through.
+    /// Channels with the same element type are not told apart.
+    pub channels: bool,
+    /// Propagate the taint of an array or slice to its length, such as in bounds checks.
+    pub taint_len: bool,
+    /// Consider every value read through a raw pointer tainted, since its provenance is not tracked.
+    pub unsafe_conservative: bool,
+    /// Functions without a taint attribute which are sources, such as those of the standard library.
+    /// Their paths are patterns, where `*` matches any sequence.
+    pub sources: Vec<SourceSpec>,
+    /// Patterns of function paths which are never sources, even if they match one of `sources`.
+    pub source_exclusions: Vec<String>,
+    /// Functions whose calls propagate taint according to the given rules, instead of their bodies.
+    pub flows: Vec<FlowSpec>,
+    /// Paths of types whose values are always tainted, such as wrappers for user input.
+    pub tainted_types: Vec<String>,
+    /// Paths of modules or types which validate everything passed through them,
+    /// so that calls from outside to the functions within them return clean values.
+    pub sanitizer_boundaries: Vec<String>,
+    /// Paths of functions whose arguments are all tainted, such as the API of a library.
+    pub entry_points: Vec<String>,
+    /// Treat every public function as an entry point.
+    pub public_entry_points: bool,
+    /// Only analyze the closures which may run when `main` or an entry point is called,
+    /// rather than all closures in the crate.
+    pub reachable_only: bool,
+    /// Path of the only function to analyze, along with the functions and closures it may call,
+    /// as an entry point. The tainted locals at each of its locations are printed, to debug its results.
+    pub function: Option<String>,
+    /// Models for rvalues which the analysis does not handle itself.
+    /// They can only be registered when using the analysis as a library.
+    pub rvalue_models: Vec<Arc<dyn RvalueModel>>,
+    /// How to treat calls to functions without a body to analyze.
+    pub unknown_call_policy: UnknownCallPolicy,
+    /// What to assume about a recursive call, while the summary of the function it calls is computed.
+    pub recursion: RecursionAssumption,
+    /// How deeply nested the summaries of called functions may be computed.
+    /// Calls beyond are treated according to `unknown_call_policy`, unless a summary for them was already computed.
+    pub max_call_depth: Option<usize>,
+    /// Path to write the violations to, in `report_format`.
+    pub report: Option<String>,
+    /// The format of the report written to `report`.
+    pub report_format: ReportFormat,
+    /// Reporters which write the results in other formats, or elsewhere, after `report` is written.
+    /// They can only be registered when using the analysis as a library.
+    pub reporters: Vec<Arc<dyn Reporter>>,
+    /// Path to a report written with `report` by an earlier run.
+    /// Violations which it already lists are neither emitted nor reported again.
+    pub baseline: Option<String>,
+    /// Path to write the violations and the tainted locals at every location to, for `taint-query`.
+    pub results: Option<String>,
+    /// Path to write a summary of the taint behavior of all public functions to.
+    pub export_summary: Option<String>,
+    /// Path to write the call graph of the analyzed functions to, in the Graphviz format.
+    pub call_graph: Option<String>,
+    /// Print a table of which arguments of each public function reach its return value, a panic or a sink.
+    pub api_table: bool,
+    /// Print the locals which may be tainted in each block of each function, under the most conservative options.
+    pub max_taint: bool,
+    /// Assume that calls to functions without a body write tainted arguments
+    /// through all of their reference arguments.
+    pub mutate_args_on_call: bool,
+    /// Patterns of function paths which are not analyzed, where `*` matches any sequence.
+    /// Calls to them are treated according to `unknown_call_policy`.
+    pub ignored: Vec<String>,
+    /// Report the MIR constructs which the analysis encountered, but does not handle.
+    pub report_unhandled: bool,
+    /// Violations with a lower confidence are not reported, but counted as suppressed.
+    /// Ignored with `enforce_sanitization`.
+    pub min_confidence: Confidence,
+    /// Require every flow from a source to a sink to pass through a sanitizer,
+    /// by reporting every tainted value which reaches a sink, whatever their categories and confidence.
+    /// The options which otherwise remove taint without a sanitizer, `clean_comparisons`, `ignored`,
+    /// `max_call_depth` and the `Ignore` policy for unknown calls, do not apply.
+    pub enforce_sanitization: bool,
+    /// Stop analyzing after the first violation, which is the only one reported.
+    pub fail_fast: bool,
+    /// Print the effective configuration instead of analyzing the crate.
+    pub dry_run: bool,
+}
+
+impl TaintConfig {
+    const PREFIX: &'static str = "--taint-";
+
+    /// Build a configuration from the `taint.toml` files above the crate being compiled,
+    /// and the `--taint-*` flags in `args`, which are removed.
+    /// Flags are applied last, so they take precedence over the files.
+    pub fn from_args(args: &mut Vec<String>) -> Self {
+        let mut config = TaintConfig::default();
+
+        // Cargo runs the driver in the workspace root, but tells it where the crate is.
+        let dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .or_else(|| std::env::current_dir().ok());
+        if let Some(dir) = dir {
+            match ConfigFile::discover(&dir) {
+                Ok(file) => file.options().iter().for_each(|option| config.set(option)),
+                Err(msg) => rustc_session::early_error(ErrorOutputType::default(), &msg),
+            }
+        }
+
+        args.retain(|arg| {
+            if let Some(option) = arg.strip_prefix(Self::PREFIX) {
+                config.set(option);
+                false
+            } else {
+                true
+            }
+        });
+        config
+    }
+
+    /// Set an option, given as a `--taint-*` flag without its prefix.
+    pub(crate) fn set(&mut self, option: &str) {
+        if let Some((key, value)) = option.split_once('=') {
+            return self.set_value(key, value);
+        }
+
+        match option {
+            "return-escape" => self.return_escape = true,
+            "panic-on-tainted-is-sink" => self.panic_on_tainted_is_sink = true,
+            "tainted-index-is-sink" => self.tainted_index_is_sink = true,
+            "panic-message-is-sink" => self.panic_message_is_sink = true,
+            "output-sinks" => self.output_sinks = true,
+            "clean-comparisons" => self.clean_comparisons = true,
+            "implicit-flows" => self.implicit_flows = true,
+            "tainted-guard-is-sink" => self.tainted_guard_is_sink = true,
+            "public-entry-points" => self.public_entry_points = true,
+            "reachable-only" => self.reachable_only = true,
+            "note-pointer-casts" => self.note_pointer_casts = true,
+            "mir-locations" => self.mir_locations = true,
+            "mutate-args-on-call" => self.mutate_args_on_call = true,
+            "unsafe-conservative" => self.unsafe_conservative = true,
+            "channels" => self.channels = true,
+            "len" => self.taint_len = true,
+            "report-unhandled" => self.report_unhandled = true,
+            "api-table" => self.api_table = true,
+            "max-taint" => self.max_taint = true,
+            "dry-run" => self.dry_run = true,
+            "fail-fast" => self.fail_fast = true,
+            "enforce-sanitization" => self.enforce_sanitization = true,
+            _ => Self::unknown_option(option),
+        }
+    }
+
+    fn set_value(&mut self, key: &str, value: &str) {
+        match key {
+            "source" => match value.strip_prefix('!') {
+                Some(pattern) => self.source_exclusions.push(pattern.to_owned()),
+                None => match SourceSpec::parse(value) {
+                    Some(spec) => self.sources.push(spec),
+                    None => Self::unknown_value(key, value),
+                },
+            },
+            "flow" => match FlowSpec::parse(value) {
+                Some(spec) => self.flows.push(spec),
+                None => Self::unknown_value(key, value),
+            },
+            "tainted-type" => self.tainted_types.push(value.to_owned()),
+            "sanitizer-boundary" => self.sanitizer_boundaries.push(value.to_owned()),
+            "sink-writer" => self.sink_writers.push(value.to_owned()),
+            "entry-point" => self.entry_points.push(value.to_owned()),
+            "function" => self.function = Some(value.to_owned()),
+            "ignore" => self.ignored.push(value.to_owned()),
+            "report" => self.report = Some(value.to_owned()),
+            "report-format" => {
+                self.report_format = match value {
+                    "text" => ReportFormat::Text,
+                    "json" => ReportFormat::Json,
+                    "sarif" => ReportFormat::Sarif,
+                    _ => Self::unknown_value(key, value),
+                }
+            }
+            "baseline" => self.baseline = Some(value.to_owned()),
+            "export-summary" => self.export_summary = Some(value.to_owned()),
+            "call-graph" => self.call_graph = Some(value.to_owned()),
+            "results" => self.results = Some(value.to_owned()),
+            "max-call-depth" => match value.parse() {
+                Ok(depth) => self.max_call_depth = Some(depth),
+                Err(_) => Self::unknown_value(key, value),
+            },
+            "min-confidence" => match Confidence::parse(value) {
+                Some(confidence) => self.min_confidence = confidence,
+                None => Self::unknown_value(key, value),
+            },
+            "unknown-call-policy" => {
+                self.unknown_call_policy = match value {
+                    "propagate-args-to-return" => UnknownCallPolicy::PropagateArgsToReturn,
+                    "taint-return" => UnknownCallPolicy::TaintReturn,
+                    "ignore" => UnknownCallPolicy::Ignore,
+                    _ => Self::unknown_value(key, value),
+                }
+            }
+            "recursion" => {
+                self.recursion = match value {
+                    "optimistic" => RecursionAssumption::Optimistic,
+                    "pessimistic" => RecursionAssumption::Pessimistic,
+                    _ => Self::unknown_value(key, value),
+                }
+            }
+            _ => Self::unknown_option(key),
+        }
+    }
+
+    /// Whether the function at `path` matches one of the `ignored` patterns.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        self.matches(PathList::Ignored, path)
+    }
+
+    fn unknown_option(option: &str) -> ! {
+        let msg = format!("unknown option `{}{}`", Self::PREFIX, option);
+        rustc_session::early_error(ErrorOutputType::default(), &msg);
+    }
+
+    fn unknown_value(key: &str, value: &str) -> ! {
+        let msg = format!("unknown value `{}` for `{}{}`", value, Self::PREFIX, key);
+        rustc_session::early_error(ErrorOutputType::default(), &msg);
+    }
+}
+
+impl AnalysisConfig for TaintConfig {
+    fn enabled(&self, option: AnalysisOption) -> bool {
+        match option {
+            AnalysisOption::PanicOnTaintedIsSink => self.panic_on_tainted_is_sink,
+            AnalysisOption::TaintedIndexIsSink => self.tainted_index_is_sink,
+            AnalysisOption::PanicMessageIsSink => self.panic_message_is_sink,
+            AnalysisOption::OutputSinks => self.output_sinks,
+            AnalysisOption::CleanComparisons => self.clean_comparisons,
+            AnalysisOption::ImplicitFlows => self.implicit_flows,
+            AnalysisOption::TaintedGuardIsSink => self.tainted_guard_is_sink,
+            AnalysisOption::NotePointerCasts => self.note_pointer_casts,
+            AnalysisOption::MirLocations => self.mir_locations,
+            AnalysisOption::Channels => self.channels,
+            AnalysisOption::TaintLen => self.taint_len,
+            AnalysisOption::UnsafeConservative => self.unsafe_conservative,
+            AnalysisOption::MutateArgsOnCall => self.mutate_args_on_call,
+            AnalysisOption::ReportUnhandled => self.report_unhandled,
+            AnalysisOption::FailFast => self.fail_fast,
+        }
+    }
+
+    fn paths(&self, list: PathList) -> &[String] {
+        match list {
+            PathList::Ignored => &self.ignored,
+            PathList::SinkWriters => &self.sink_writers,
+            PathList::SanitizerBoundaries => &self.sanitizer_boundaries,
+            PathList::TaintedTypes => &self.tainted_types,
+        }
+    }
+
+    /// The first source configured for the function at `path`, unless it is excluded.
+    fn source(&self, path: &str) -> Option<&SourceSpec> {
+        if self
+            .source_exclusions
+            .iter()
+            .any(|pattern| glob_match(pattern.as_bytes(), path.as_bytes()))
+        {
+            return None;
+        }
+
+        self.sources
+            .iter()
+            .find(|spec| glob_match(spec.path.as_bytes(), path.as_bytes()))
+    }
+
+    fn flow(&self, path: &str) -> Option<&FlowSpec> {
+        self.flows.iter().find(|spec| spec.path == path)
+    }
+
+    fn unknown_call_policy(&self) -> UnknownCallPolicy {
+        self.unknown_call_policy
+    }
+
+    fn recursion(&self) -> RecursionAssumption {
+        self.recursion
+    }
+
+    fn max_call_depth(&self) -> Option<usize> {
+        self.max_call_depth
+    }
+
+    fn min_confidence(&self) -> Confidence {
+        self.min_confidence
+    }
+
+    fn rvalue_models(&self) -> &[Arc<dyn RvalueModel>] {
+        &self.rvalue_models
+    }
+
+    /// With `function`, only the states of the function being debugged are recorded.
+    fn records_states(&self, path: &str) -> bool {
+        self.results.is_some() || self.function.as_deref() == Some(path)
+    }
+}
+
+/// The format of the report written with `--taint-report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Each violation along with the source around it, which is the only format `--taint-baseline` reads.
+    Text,
+    /// The violations, unhandled constructs and the status of each sink, as JSON.
+    Json,
+    /// The violations as a SARIF 2.1.0 log.
+    Sarif,
+}
+
+impl Default for ReportFormat {
+    fn default() -> Self {
+        ReportFormat::Text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{analyze, INPUT_OUTPUT};
+
+    #[test]
+    fn excluded_sources() {
+        let mut config = TaintConfig::default();
+        config.set("source=std::env::*");
+        config.set("source=!std::env::current_dir");
+
+        assert!(config.source("std::env::var").is_some());
+        assert!(config.source("std::env::current_dir").is_none());
+        assert!(config.source("std::fs::read").is_none());
+    }
+
+    #[test]
+    fn sanitizer_boundaries() {
+        let mut config = TaintConfig::default();
+        config.set("sanitizer-boundary=app::validate");
+        config.set("sanitizer-boundary=app::Input");
+
+        assert!(config.matches(PathList::SanitizerBoundaries, "app::validate"));
+        assert!(config.matches(PathList::SanitizerBoundaries, "app::validate::email"));
+        assert!(config.matches(PathList::SanitizerBoundaries, "app::Input::parse"));
+        assert!(!config.matches(PathList::SanitizerBoundaries, "app::validated::email"));
+        assert!(!config.matches(PathList::SanitizerBoundaries, "app::InputReader::read"));
+        assert!(!config.matches(PathList::SanitizerBoundaries, "app"));
+    }
+
+    #[test]
+    fn fail_fast_reports_one_violation() {
+        let program = &[
+            r#"
+fn main() {
+    let value = input();
+    output(value);
+    output(value);
+    let f = move || output(value);
+    f();
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        assert_eq!(analyze(program, TaintConfig::default()).violations.len(), 3);
+
+        let config = TaintConfig {
+            fail_fast: true,
+            ..TaintConfig::default()
+        };
+        let found = analyze(program, config).violations;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.iter().next().unwrap().location, "<program.rs>:4:5");
+    }
+
+    #[test]
+    fn low_confidence_violations_are_suppressed() {
+        let program = &[
+            r#"
+fn main() {
+    let value = input();
+    output(value);
+    output(value * 2);
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        let config = TaintConfig {
+            min_confidence: Confidence::High,
+            ..TaintConfig::default()
+        };
+        let report = analyze(program, config);
+
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(
+            report.violations.iter().next().unwrap().location,
+            "<program.rs>:4:5"
+        );
+        assert_eq!(report.suppressed, 1);
+        assert_eq!(analyze(program, TaintConfig::default()).suppressed, 0);
+    }
+
+    #[test]
+    fn confidence_is_joined_across_paths() {
+        let program = &[
+            r#"
+fn main() {
+    let value = input();
+    let chosen = if flag() { value } else { value * 2 };
+    output(chosen);
+    let chosen = if flag() { value * 2 } else { value };
+    output(chosen);
+}
+
+fn flag() -> bool {
+    true
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        let config = TaintConfig {
+            min_confidence: Confidence::High,
+            ..TaintConfig::default()
+        };
+        let report = analyze(program, config);
+
+        // Each sink is reached directly on one of the paths, whichever of them is visited first.
+        assert_eq!(report.violations.len(), 2);
+        assert_eq!(report.suppressed, 0);
+    }
+
+    #[test]
+    fn baseline_violations_are_not_reported() {
+        let program = &[
+            r#"
+fn main() {
+    let value = input();
+    output(value);
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        let old = analyze(program, TaintConfig::default());
+        assert_eq!(old.violations.len(), 1);
+
+        let baseline = std::env::temp_dir().join(format!("taint-baseline-{}", std::process::id()));
+        std::fs::write(&baseline, old.to_text()).unwrap();
+
+        // Lines added above a violation in the baseline, including right above the call, do not make it new.
+        let changed = program
+            .replace(
+                "    output(value);",
+                "    let doubled = value * 2;\n    output(value);\n    output(doubled);",
+            )
+            .replace("fn main() {", "fn main() {\n");
+        let config = TaintConfig {
+            baseline: Some(baseline.to_str().unwrap().to_owned()),
+            ..TaintConfig::default()
+        };
+        let found = analyze(&changed, config).violations;
+        std::fs::remove_file(&baseline).unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found.iter().next().unwrap().location, "<program.rs>:7:5");
+    }
+
+    #[test]
+    fn recursion_assumptions() {
+        let program = &[
+            r#"
+fn main() {
+    let secret = input();
+    let mut slot = 0;
+    output(store(&mut slot, secret, 3));
+    output(depth(secret, 3));
+}
+
+fn store(slot: &mut i32, value: i32, n: u32) -> i32 {
+    if n == 0 {
+        *slot = value;
+        0
+    } else {
+        store(slot, value, n - 1);
+        *slot
+    }
+}
+
+fn depth(value: i32, n: u32) -> i32 {
+    if n == 0 {
+        0
+    } else {
+        depth(value, n - 1)
+    }
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        let locations = |recursion| {
+            let config = TaintConfig {
+                recursion,
+                ..TaintConfig::default()
+            };
+            analyze(program, config)
+                .violations
+                .into_iter()
+                .map(|v| v.location)
+                .collect::<Vec<_>>()
+        };
+
+        // Only the second computation of the summary of `store` finds that `*slot` is tainted.
+        assert_eq!(
+            locations(RecursionAssumption::Optimistic),
+            vec!["<program.rs>:5:5"]
+        );
+        // `depth` never returns `value`, but its recursive call is assumed to.
+        assert_eq!(
+            locations(RecursionAssumption::Pessimistic),
+            vec!["<program.rs>:5:5", "<program.rs>:6:5"]
+        );
+    }
+
+    #[test]
+    fn max_call_depth() {
+        let program = &[
+            r#"
+fn main() {
+    output(first(input()));
+}
+
+fn first(value: i32) -> i32 {
+    second(value)
+}
+
+fn second(value: i32) -> i32 {
+    third(value)
+}
+
+fn third(_value: i32) -> i32 {
+    0
+}
+"#,
+            INPUT_OUTPUT,
+        ]
+        .concat();
+        let report = |max_call_depth| {
+            let config = TaintConfig {
+                max_call_depth,
+                ..TaintConfig::default()
+            };
+            analyze(program, config)
+        };
+
+        let unlimited = report(None);
+        assert!(unlimited.violations.is_empty());
+        assert!(unlimited.depth_limited.is_empty());
+        assert!(report(Some(3)).violations.is_empty());
+
+        // `third` is not summarized, so its return value is tainted by its tainted argument.
+        let limited = report(Some(2));
+        assert_eq!(limited.violations.len(), 1);
+        assert_eq!(
+            limited.depth_limited.iter().collect::<Vec<_>>(),
+            vec!["third"]
+        );
+    }
+
+    #[test]
+    fn single_function() {
+        let report = analyze(
+            &[
+                r#"
+fn main() {
+    output(input());
+    helper(1);
+}
+
+fn helper(value: i32) {
+    let copy = twice(value);
+    output(copy);
+}
+
+fn twice(value: i32) -> i32 {
+    value * 2
+}
+"#,
+                INPUT_OUTPUT,
+            ]
+            .concat(),
+            TaintConfig {
+                function: Some("helper".to_owned()),
+                ..TaintConfig::default()
+            },
+        );
+
+        // Only `helper` is analyzed, with its argument tainted, and `main` is not.
+        let found = report.violations.iter().collect::<Vec<_>>();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].function, "helper");
+        assert_eq!(
+            report.states.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["helper"]
+        );
+
+        let states = report.to_states();
+        assert!(states.starts_with("helper:\n    bb0[0]: _1\n"));
+        assert!(!states.contains("twice:"));
+    }
+
+    #[test]
+    fn enforce_sanitization() {
+        let program = r#"
+fn main() {
+    let path = read_file();
+    query(path * 2);
+    query(clean(path) * 2);
+}
+
+#[taint::source(filesystem)]
+fn read_file() -> i32 {
+    1
+}
+
+#[taint::sanitizer]
+fn clean(value: i32) -> i32 {
+    value
+}
+
+#[taint::sink(sql)]
+fn query(_: i32) {}
+"#;
+        let config = TaintConfig {
+            min_confidence: Confidence::High,
+            ..TaintConfig::default()
+        };
+        assert!(analyze(program, config.clone()).violations.is_empty());
+
+        // Neither the category of the sink nor the confidence of the flow excuse the missing sanitizer.
+        let config = TaintConfig {
+            enforce_sanitization: true,
+            ..config
+        };
+        let found = analyze(program, config).violations;
+        assert_eq!(found.len(), 1);
+        let violation = found.iter().next().unwrap();
+        assert_eq!(violation.location, "<program.rs>:4:5");
+        assert_eq!(violation.source.as_deref(), Some("<program.rs>:3:16"));
+    }
+
+    #[test]
+    fn enforce_sanitization_overrides_options() {
+        let program = r#"
+fn main() {
+    let value = input();
+    query((value == 1) as i32);
+    query(unsafe { unknown(value) });
+    query(launder(value));
+    query(deep(value));
+}
+
+fn launder(value: i32) -> i32 {
+    value
+}
+
+fn deep(value: i32) -> i32 {
+    value + 1
+}
+
+extern "Rust" {
+    fn unknown(_: i32) -> i32;
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn query(_: i32) {}
+"#;
+        let config = TaintConfig {
+            clean_comparisons: true,
+            unknown_call_policy: UnknownCallPolicy::Ignore,
+            ignored: vec!["launder".to_owned()],
+            max_call_depth: Some(0),
+            ..TaintConfig::default()
+        };
+        assert!(analyze(program, config.clone()).violations.is_empty());
+
+        // Only sanitizers remove taint.
+        let config = TaintConfig {
+            enforce_sanitization: true,
+            ..config
+        };
+        let found = analyze(program, config).violations;
+        assert_eq!(
+            found
+                .iter()
+                .map(|v| v.location.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "<program.rs>:4:5",
+                "<program.rs>:5:5",
+                "<program.rs>:6:5",
+                "<program.rs>:7:5"
+            ]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Configuration files named `taint.toml`, which hold the same options as the `--taint-*` flags.
+//!
+//! Only a subset of TOML is supported: each line is empty, a comment, or `<option> = <value>`,
+//! where `<value>` is `true` or `false` for flags, a string, or an array of strings on one line
+//! for options which may be repeated:
+//!
+//! ```toml
+//! # Shared by all crates in the workspace.
+//! implicit-flows = true
+//! source = ["std::env::var", "std::io::Stdin::read_line(1)"]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+pub const FILE_NAME: &str = "taint.toml";
+
+/// Whether `dir` holds the manifest of a workspace, or is the root of a git repository.
+fn is_project_root(dir: &Path) -> bool {
+    let workspace = std::fs::read_to_string(dir.join("Cargo.toml")).map_or(false, |manifest| {
+        manifest.lines().any(|line| line.trim() == "[workspace]")
+    });
+    workspace || dir.join(".git").exists()
+}
+
+/// The options of one or more configuration files.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    /// The values of each option, in the order they were given.
+    /// Flags have a single value, `true` or `false`.
+    entries: BTreeMap<String, Vec<String>>,
+}
+
+impl ConfigFile {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut file = ConfigFile::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |msg: &str| format!("line {}: {}", number + 1, msg);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `<option> = <value>`"))?;
+            let values = parse_value(value.trim()).ok_or_else(|| error("invalid value"))?;
+            file.entries.insert(key.trim().to_owned(), values);
+        }
+
+        Ok(file)
+    }
+
+    /// Merge the configuration files from the root of the project down to `dir`.
+    /// Files closer to `dir`, such as that of a crate, override those further up, such as that of its workspace.
+    /// The root is the first directory from `dir` up which holds the manifest of a workspace or a repository,
+    /// so that files outside the project, such as in the home directory, are not read.
+    pub fn discover(dir: &Path) -> Result<Self, String> {
+        let mut paths = Vec::new();
+        for dir in dir.ancestors() {
+            let path = dir.join(FILE_NAME);
+            if path.is_file() {
+                paths.push(path);
+            }
+            if is_project_root(dir) {
+                break;
+            }
+        }
+        paths.reverse();
+
+        let mut file = ConfigFile::default();
+        for path in paths {
+            let text = std::fs::read_to_string(&path)
+                .map_err(|err| format!("failed to read `{}`: {}", path.display(), err))?;
+            let other = ConfigFile::parse(&text)
+                .map_err(|err| format!("failed to parse `{}`: {}", path.display(), err))?;
+            file.merge(other);
+        }
+        Ok(file)
+    }
+
+    /// Replace the options which `other` sets, including all values of repeated options.
+    pub fn merge(&mut self, other: ConfigFile) {
+        self.entries.extend(other.entries);
+    }
+
+    /// The options in the form of the `--taint-*` flags, without their prefix.
+    pub fn options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+        for (key, values) in self.entries.iter() {
+            match values.as_slice() {
+                [flag] if flag == "true" => options.push(key.clone()),
+                [flag] if flag == "false" => {}
+                values => options.extend(values.iter().map(|value| format!("{}={}", key, value))),
+            }
+        }
+        options
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = String::new();
+        for (key, values) in self.entries.iter() {
+            let quoted = values
+                .iter()
+                .map(|value| match value.as_str() {
+                    "true" | "false" => value.clone(),
+                    _ => format!("{:?}", value),
+                })
+                .collect::<Vec<_>>();
+            let _ = match quoted.as_slice() {
+                [value] => writeln!(text, "{} = {}", key, value),
+                _ => writeln!(text, "{} = [{}]", key, quoted.join(", ")),
+            };
+        }
+        text
+    }
+}
+
+/// A flag, a string or an array of strings.
+fn parse_value(value: &str) -> Option<Vec<String>> {
+    if value == "true" || value == "false" {
+        return Some(vec![value.to_owned()]);
+    }
+
+    let mut rest = match value.strip_prefix('[') {
+        Some(rest) => rest.trim_start(),
+        None => {
+            let (string, rest) = parse_string(value)?;
+            return rest.trim().is_empty().then(|| vec![string]);
+        }
+    };
+
+    let mut values = Vec::new();
+    loop {
+        if let Some(end) = rest.strip_prefix(']') {
+            return end.trim().is_empty().then(|| values);
+        }
+
+        let (string, after) = parse_string(rest)?;
+        values.push(string);
+        rest = after.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(']') {
+            return None;
+        }
+    }
+}
+
+/// A string in double quotes at the start of `text`, and the text after it.
+fn parse_string(text: &str) -> Option<(String, &str)> {
+    let text = text.strip_prefix('"')?;
+    let mut string = String::new();
+    let mut chars = text.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((string, &text[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                '"' => string.push('"'),
+                '\\' => string.push('\\'),
+                _ => return None,
+            },
+            c => string.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::config::TaintConfig;
+
+    const ROOT: &str = r#"
+# Shared by the whole workspace.
+implicit-flows = true
+source = ["std::env::var", "std::io::Stdin::read_line(1)"]
+ignore = "tests::*"
+"#;
+
+    const CRATE: &str = r#"
+source = ["std::fs::read_to_string"]
+flow = ["pick(arg1 -> ret, arg0 -> ret)"]
+implicit-flows = false
+"#;
+
+    #[test]
+    fn parse_values() {
+        let file = ConfigFile::parse(ROOT).unwrap();
+        assert_eq!(
+            file.options(),
+            vec![
+                "ignore=tests::*",
+                "implicit-flows",
+                "source=std::env::var",
+                "source=std::io::Stdin::read_line(1)",
+            ]
+        );
+
+        assert!(ConfigFile::parse("source").is_err());
+        assert!(ConfigFile::parse("source = [\"a\" \"b\"]").is_err());
+        assert!(ConfigFile::parse("source = \"a").is_err());
+    }
+
+    #[test]
+    fn crate_overrides_root() {
+        let mut file = ConfigFile::parse(ROOT).unwrap();
+        file.merge(ConfigFile::parse(CRATE).unwrap());
+
+        let mut config = TaintConfig::default();
+        for option in file.options() {
+            config.set(&option);
+        }
+
+        let sources = config
+            .sources
+            .iter()
+            .map(|spec| spec.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(sources, vec!["std::fs::read_to_string"]);
+        assert_eq!(config.flows[0].path, "pick");
+        assert_eq!(config.ignored, vec!["tests::*"]);
+        assert!(!config.implicit_flows);
+
+        assert_eq!(ConfigFile::parse(&file.to_toml()).unwrap(), file);
+    }
+
+    #[test]
+    fn discover_files() {
+        let root = std::env::temp_dir().join(format!("taint-config-{}", std::process::id()));
+        let member = root.join("member");
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(root.join(FILE_NAME), ROOT).unwrap();
+        std::fs::write(member.join(FILE_NAME), CRATE).unwrap();
+
+        let discovered = ConfigFile::discover(&member);
+        let at_root = ConfigFile::discover(&root);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let mut merged = ConfigFile::parse(ROOT).unwrap();
+        merged.merge(ConfigFile::parse(CRATE).unwrap());
+        assert_eq!(discovered.unwrap(), merged);
+        assert_eq!(at_root.unwrap(), ConfigFile::parse(ROOT).unwrap());
+    }
+
+    #[test]
+    fn discover_stops_at_workspace() {
+        let outside = std::env::temp_dir().join(format!("taint-workspace-{}", std::process::id()));
+        let root = outside.join("workspace");
+        let member = root.join("member");
+        std::fs::create_dir_all(&member).unwrap();
+        std::fs::write(outside.join(FILE_NAME), "fail-fast = true").unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n",
+        )
+        .unwrap();
+        std::fs::write(root.join(FILE_NAME), ROOT).unwrap();
+        std::fs::write(member.join(FILE_NAME), CRATE).unwrap();
+
+        let discovered = ConfigFile::discover(&member);
+        std::fs::remove_dir_all(&outside).unwrap();
+
+        let mut merged = ConfigFile::parse(ROOT).unwrap();
+        merged.merge(ConfigFile::parse(CRATE).unwrap());
+        assert_eq!(discovered.unwrap(), merged);
+    }
+}
@@ -1,19 +1,535 @@
-use rustc_hir::def_id::DefId;
-use rustc_middle::ty::TyCtxt;
-use rustc_mir::dataflow::Analysis;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    rc::Rc,
+};
 
-use crate::eval::attributes::TaintAttributeFinder;
-use crate::taint_analysis::TaintAnalysis;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_middle::mir::{Local, TerminatorKind};
+use rustc_middle::ty::{subst::GenericArgKind, Instance, TyCtxt, TyKind, Visibility};
+
+use crate::analysis_config::UnknownCallPolicy;
+use crate::attributes::{AttrInfo, CategoryFilter, TaintAttributeFinder};
+use crate::errors::TaintedReturn;
+use crate::eval::config::TaintConfig;
+use crate::eval::max_taint::max_taint;
+use crate::eval::summary::summarize_api;
+use crate::report::{merge_violations, CallEdge, CrateReport, UnhandledConstruct};
+use crate::taint_analysis::{Shared, TaintAnalysis};
+use crate::violation::{Confidence, SinkStatus};
+
+pub fn eval_main(tcx: TyCtxt<'_>, main_id: DefId, config: &TaintConfig) -> CrateReport {
+    // When sanitization is enforced, only sanitizers remove taint,
+    // so the options which otherwise drop it on the way to a sink are overridden.
+    let enforced;
+    let config = if config.enforce_sanitization {
+        enforced = TaintConfig {
+            clean_comparisons: false,
+            unknown_call_policy: match config.unknown_call_policy {
+                UnknownCallPolicy::Ignore => UnknownCallPolicy::PropagateArgsToReturn,
+                policy => policy,
+            },
+            ignored: Vec::new(),
+            max_call_depth: None,
+            min_confidence: Confidence::Low,
+            ..config.clone()
+        };
+        &enforced
+    } else {
+        config
+    };
 
-pub fn eval_main(tcx: TyCtxt<'_>, main_id: DefId) {
     // Find all functions in the current crate that have been tagged
     let mut finder = TaintAttributeFinder::new(tcx);
     tcx.hir().krate().visit_all_item_likes(&mut finder);
 
-    let entry = tcx.optimized_mir(main_id);
+    let shared = Rc::new(RefCell::new(Shared::default()));
+    // The statics, channels and closures which are tainted in any round, for checking the public functions.
+    let mut converged = Shared {
+        silent: true,
+        ..Shared::default()
+    };
+
+    if let Some(path) = &config.baseline {
+        match std::fs::read_to_string(path) {
+            Ok(text) => {
+                shared.borrow_mut().baseline = CrateReport::from_text(&text)
+                    .violations
+                    .iter()
+                    .map(|v| {
+                        let (function, sink, location) = v.key();
+                        (function.to_owned(), sink.to_owned(), location.to_owned())
+                    })
+                    .collect();
+            }
+            Err(err) => tcx.sess.err(&format!(
+                "failed to read taint baseline from `{}`: {}",
+                path, err
+            )),
+        }
+    }
+
+    // With `--taint-function`, only that function is analyzed, as an entry point,
+    // along with the functions and closures which it may call.
+    let focus = match &config.function {
+        Some(path) => match tcx
+            .body_owners()
+            .map(|id| id.to_def_id())
+            .find(|id| tcx.def_path_str(*id) == *path)
+        {
+            Some(id) => Some(id),
+            None => {
+                tcx.sess
+                    .err(&format!("no function `{}` to analyze in this crate", path));
+                return CrateReport::default();
+            }
+        },
+        None => None,
+    };
+
+    let reachable = match focus {
+        Some(id) => Some(reachable_bodies(tcx, vec![id])),
+        None => config.reachable_only.then(|| {
+            let roots = std::iter::once(main_id)
+                .chain(
+                    tcx.body_owners()
+                        .map(|id| id.to_def_id())
+                        .filter(|id| is_entry_point(tcx, &finder.info, config, *id)),
+                )
+                .collect();
+            reachable_bodies(tcx, roots)
+        }),
+    };
+
+    // Taint is a single bit, so sinks which only care about some categories of sources
+    // are checked in a separate round for each category.
+    // When sanitization is enforced, every sink is checked against every source in a single round.
+    let filters = if config.enforce_sanitization {
+        vec![CategoryFilter::Any]
+    } else {
+        finder.info.category_filters()
+    };
+    for filter in filters {
+        let found = {
+            let mut shared = shared.borrow_mut();
+            shared.categories = filter;
+            // Summaries, and the taint of closures, channels and statics depend on the sources matched.
+            shared.contexts.clear();
+            shared.callbacks.clear();
+            shared.captures.clear();
+            shared.tainted_channels.clear();
+            shared.tainted_statics.clear();
+            std::mem::take(&mut shared.violations)
+        };
+
+        eval_round(
+            tcx,
+            &finder.info,
+            config,
+            main_id,
+            focus,
+            reachable.as_ref(),
+            &shared,
+        );
+
+        // A sink with several categories is checked in the round of each, and its violations are joined.
+        let mut shared = shared.borrow_mut();
+        converged
+            .tainted_statics
+            .extend(shared.tainted_statics.iter().copied());
+        converged
+            .tainted_channels
+            .extend(shared.tainted_channels.iter().copied());
+        for (id, (captures, arguments)) in &shared.captures {
+            let joined = converged.captures.entry(*id).or_default();
+            joined.0 |= captures;
+            joined.1 |= arguments;
+        }
+
+        let round = std::mem::replace(&mut shared.violations, found);
+        merge_violations(&mut shared.violations, round);
+        if config.fail_fast && !shared.violations.is_empty() {
+            break;
+        }
+    }
+
+    if config.return_escape {
+        check_return_escape(tcx, &finder.info, config, converged);
+    }
+
+    let api = (config.export_summary.is_some() || config.api_table)
+        .then(|| summarize_api(tcx, &finder.info, config));
+    let max_taint = config
+        .max_taint
+        .then(|| max_taint(tcx, &finder.info, config));
+
+    let mut shared = shared.borrow_mut();
+    let violations = std::mem::take(&mut shared.violations);
+    let unhandled = collect_unhandled(tcx, &shared.unhandled);
+    let sinks = finder
+        .info
+        .sinks
+        .iter()
+        .map(|id| {
+            let status = shared.sinks.get(id).copied();
+            (
+                tcx.def_path_str(*id),
+                status.unwrap_or(SinkStatus::Unreached),
+            )
+        })
+        .collect();
+
+    let states = shared
+        .states
+        .iter()
+        .map(|(id, states)| {
+            let mut states = states
+                .iter()
+                .map(|(location, state)| (*location, state.iter().collect::<Vec<_>>()))
+                .collect::<Vec<_>>();
+            states.sort_by_key(|(location, _)| (location.block, location.statement_index));
+            let states = states
+                .into_iter()
+                .map(|(location, locals)| {
+                    let locals = locals.iter().map(|local| format!("{:?}", local)).collect();
+                    (format!("{:?}", location), locals)
+                })
+                .collect();
+            (tcx.def_path_str(*id), states)
+        })
+        .collect();
+
+    let source_map = tcx.sess.source_map();
+    let calls = shared
+        .calls
+        .iter()
+        .map(|(caller, callee, span)| {
+            let loc = source_map.lookup_char_pos(span.lo());
+            CallEdge {
+                caller: tcx.def_path_str(*caller),
+                callee: tcx.def_path_str(*callee),
+                location: format!("{}:{}:{}", loc.file.name, loc.line, loc.col_display + 1),
+            }
+        })
+        .collect();
+
+    let depth_limited = std::mem::take(&mut shared.depth_limited);
+    if let Some(function) = depth_limited.iter().next() {
+        tcx.sess.note_without_error(&format!(
+            "calls to {} function(s), e.g. `{}`, were beyond `--taint-max-call-depth` and treated like calls to functions without a body",
+            depth_limited.len(),
+            function
+        ));
+    }
+
+    let suppressed = shared.suppressed.len();
+    if suppressed > 0 {
+        tcx.sess.note_without_error(&format!(
+            "{} violation(s) with a confidence below `{}` were suppressed",
+            suppressed,
+            config.min_confidence.as_str()
+        ));
+    }
+
+    CrateReport {
+        violations,
+        unhandled,
+        api,
+        sinks,
+        suppressed,
+        states,
+        calls,
+        depth_limited,
+        max_taint,
+    }
+}
+
+/// Analyze the crate, matching the sources and sinks of the current category filter.
+fn eval_round<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    info: &AttrInfo,
+    config: &TaintConfig,
+    main_id: DefId,
+    focus: Option<DefId>,
+    reachable: Option<&HashSet<DefId>>,
+    shared: &Rc<RefCell<Shared<'tcx>>>,
+) {
+    // A static may be tainted in one body and read in another which was analyzed before it,
+    // a channel may be sent a tainted value in a closure and received from in `main`,
+    // and a closure may be passed tainted data in a closure analyzed after it,
+    // so analyze all bodies silently until no more statics, channels or closures are tainted, then once more to report.
+    // Statics are not flow-sensitive: a read is tainted if a tainted value is written
+    // anywhere in the crate, even if the write happens after the read, and they are never cleaned.
+    // Writes through references to a static, rather than to the static itself, are not tracked.
+    let has_mutable_statics = tcx
+        .body_owners()
+        .any(|id| tcx.is_mutable_static(id.to_def_id()));
+    let has_closures = tcx
+        .mir_keys(LOCAL_CRATE)
+        .iter()
+        .any(|id| tcx.is_closure(id.to_def_id()));
+    if has_mutable_statics || config.channels || has_closures {
+        let tainted = |shared: &Shared<'_>| {
+            (
+                shared.tainted_statics.len(),
+                shared.tainted_channels.len(),
+                shared.captures.clone(),
+            )
+        };
+        shared.borrow_mut().silent = true;
+        loop {
+            let before = tainted(&shared.borrow());
+            eval_bodies(tcx, info, config, main_id, focus, reachable, shared);
+
+            let mut shared = shared.borrow_mut();
+            // Summaries depend on the taint of statics, channels and closures.
+            shared.contexts.clear();
+            shared.callbacks.clear();
+            if tainted(&shared) == before {
+                break;
+            }
+        }
+
+        let mut shared = shared.borrow_mut();
+        shared.silent = false;
+        shared.violations.clear();
+        shared.suppressed.clear();
+    }
+
+    eval_bodies(tcx, info, config, main_id, focus, reachable, shared);
+}
+
+/// Analyze `main`, the entry points and the closures of the crate,
+/// or only `focus` and the closures it may call, with all of its arguments tainted.
+fn eval_bodies<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    info: &AttrInfo,
+    config: &TaintConfig,
+    main_id: DefId,
+    focus: Option<DefId>,
+    reachable: Option<&HashSet<DefId>>,
+    shared: &Rc<RefCell<Shared<'tcx>>>,
+) {
+    let stopped = || config.fail_fast && !shared.borrow().violations.is_empty();
+    if let Some(id) = focus {
+        let body = tcx.optimized_mir(id);
+        let init = vec![Some(true); body.arg_count];
+        let _ = TaintAnalysis::new_with_init(tcx, info, config, body, shared.clone(), init)
+            .into_exit_state();
+
+        if !stopped() {
+            eval_closures(tcx, info, config, reachable, shared);
+        }
+        return;
+    }
+
+    if !config.is_ignored(&tcx.def_path_str(main_id)) {
+        let entry = tcx.optimized_mir(main_id);
+        let _ = TaintAnalysis::new_with_init(tcx, info, config, entry, shared.clone(), Vec::new())
+            .into_exit_state();
+    }
+
+    if !stopped() {
+        eval_entry_points(tcx, info, config, main_id, shared);
+    }
+    if !stopped() {
+        eval_closures(tcx, info, config, reachable, shared);
+    }
+}
+
+/// Summarize the locations of each unhandled construct, and list them as notes.
+fn collect_unhandled(
+    tcx: TyCtxt<'_>,
+    unhandled: &BTreeMap<&'static str, BTreeSet<(String, String)>>,
+) -> BTreeMap<String, UnhandledConstruct> {
+    let mut result = BTreeMap::new();
+
+    for (name, locations) in unhandled.iter() {
+        if let Some((function, location)) = locations.iter().next() {
+            tcx.sess.note_without_error(&format!(
+                "unhandled MIR construct `{}` at {} location(s), e.g. in `{}` at {}",
+                name,
+                locations.len(),
+                function,
+                location
+            ));
+
+            let construct = UnhandledConstruct {
+                count: locations.len(),
+                function: function.clone(),
+                location: location.clone(),
+            };
+            result.insert(name.to_string(), construct);
+        }
+    }
+
+    result
+}
+
+/// Entry points are analyzed with all of their arguments tainted,
+/// since they are the boundary at which untrusted data enters.
+fn eval_entry_points<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    info: &AttrInfo,
+    config: &TaintConfig,
+    main_id: DefId,
+    shared: &Rc<RefCell<Shared<'tcx>>>,
+) {
+    if config.entry_points.is_empty() && !config.public_entry_points {
+        return;
+    }
+
+    for local_id in tcx.body_owners() {
+        let id = local_id.to_def_id();
+        if id == main_id || !is_entry_point(tcx, info, config, id) {
+            continue;
+        }
+
+        let body = tcx.optimized_mir(id);
+        let init = vec![Some(true); body.arg_count];
+
+        let _ = TaintAnalysis::new_with_init(tcx, info, config, body, shared.clone(), init)
+            .into_exit_state();
+    }
+}
+
+/// Whether `id` is a function whose arguments are all tainted, configured with `entry_points`
+/// or `public_entry_points`. Annotated and ignored functions are never entry points.
+fn is_entry_point(tcx: TyCtxt<'_>, info: &AttrInfo, config: &TaintConfig, id: DefId) -> bool {
+    if !matches!(tcx.def_kind(id), DefKind::Fn | DefKind::AssocFn)
+        || info.get_kind(&id).is_some()
+        || config.is_ignored(&tcx.def_path_str(id))
+    {
+        return false;
+    }
+
+    (config.public_entry_points && tcx.visibility(id) == Visibility::Public)
+        || config.entry_points.contains(&tcx.def_path_str(id))
+}
+
+/// The local bodies which may run when one of `roots`, such as `main` or an entry point, is called:
+/// the functions which they call, directly or through a trait,
+/// and the functions and closures which they pass around, such as callbacks.
+fn reachable_bodies(tcx: TyCtxt<'_>, roots: Vec<DefId>) -> HashSet<DefId> {
+    let mut pending = roots;
+    let mut reachable = HashSet::new();
+    while let Some(id) = pending.pop() {
+        if !id.is_local() || !tcx.is_mir_available(id) || !reachable.insert(id) {
+            continue;
+        }
+
+        let body = tcx.optimized_mir(id);
+        let param_env = tcx.param_env(id);
+        let mut types = body
+            .local_decls
+            .iter()
+            .map(|decl| decl.ty)
+            .collect::<Vec<_>>();
+        for data in body.basic_blocks() {
+            if let TerminatorKind::Call { func, .. } = &data.terminator().kind {
+                types.push(func.ty(body, tcx));
+            }
+        }
+
+        // Functions and closures which are called or passed along appear in these types,
+        // such as the type of a callee, or the closure type in its generic arguments.
+        for ty in types.iter().flat_map(|ty| ty.walk()) {
+            let ty = match ty.unpack() {
+                GenericArgKind::Type(ty) => ty,
+                _ => continue,
+            };
+            match *ty.kind() {
+                TyKind::FnDef(callee, substs) => {
+                    pending.push(callee);
+                    if let Ok(Some(instance)) = Instance::resolve(tcx, param_env, callee, substs) {
+                        pending.push(instance.def_id());
+                    }
+                }
+                TyKind::Closure(closure, _) | TyKind::Generator(closure, _, _) => {
+                    pending.push(closure)
+                }
+                _ => {}
+            }
+        }
+    }
+    reachable
+}
+
+/// Closures and generators have bodies of their own, which calls from `main` do not reach.
+/// Their captured environment is tainted if the closure was, when it was passed to a call,
+/// and their other arguments if the call was passed other tainted data.
+/// With `reachable_only` or `function`, only the closures in `reachable` are analyzed.
+fn eval_closures<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    info: &AttrInfo,
+    config: &TaintConfig,
+    reachable: Option<&HashSet<DefId>>,
+    shared: &Rc<RefCell<Shared<'tcx>>>,
+) {
+    let mut closures = tcx
+        .mir_keys(LOCAL_CRATE)
+        .iter()
+        .map(|id| id.to_def_id())
+        .filter(|id| tcx.is_closure(*id) && !config.is_ignored(&tcx.def_path_str(*id)))
+        .filter(|id| reachable.map_or(true, |reachable| reachable.contains(id)))
+        .collect::<Vec<_>>();
+
+    // Outer closures are defined first, so their calls are usually seen before inner closures are analyzed,
+    // though the taint they pass is only known for sure once no more closures are tainted.
+    closures.sort();
+
+    for id in closures {
+        let (captures, arguments) = shared
+            .borrow()
+            .captures
+            .get(&id)
+            .copied()
+            .unwrap_or_default();
+        let body = tcx.optimized_mir(id);
+        let mut init = vec![Some(captures)];
+        init.resize(body.arg_count, Some(arguments));
+
+        let _ = TaintAnalysis::new_with_init(tcx, info, config, body, shared.clone(), init)
+            .into_exit_state();
+    }
+}
+
+/// Warn about public functions which may return a tainted value,
+/// even though all of their arguments are untainted.
+/// Annotated functions are skipped, since their behavior is already known.
+/// The statics, channels and closures which the analysis of the crate found tainted in `converged` stay tainted.
+fn check_return_escape<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    info: &AttrInfo,
+    config: &TaintConfig,
+    converged: Shared<'tcx>,
+) {
+    // Sinks in the public functions were already reported, so their violations are only recorded.
+    let shared = Rc::new(RefCell::new(converged));
+    for local_id in tcx.body_owners() {
+        let id = local_id.to_def_id();
+
+        if !matches!(tcx.def_kind(id), DefKind::Fn | DefKind::AssocFn)
+            || tcx.visibility(id) != Visibility::Public
+            || info.get_kind(&id).is_some()
+            || config.is_ignored(&tcx.def_path_str(id))
+        {
+            continue;
+        }
+
+        let body = tcx.optimized_mir(id);
+        let exit_state =
+            TaintAnalysis::new_with_init(tcx, info, config, body, shared.clone(), Vec::new())
+                .into_exit_state();
 
-    let _ = TaintAnalysis::new(tcx, &finder.info)
-        .into_engine(tcx, entry)
-        .pass_name("taint_analysis")
-        .iterate_to_fixpoint();
+        if let Some(state) = exit_state {
+            if state.contains(Local::from_usize(0)) {
+                tcx.sess.emit_err(TaintedReturn {
+                    fn_name: tcx.def_path_str(id),
+                    span: tcx.def_span(id),
+                });
+            }
+        }
+    }
 }
@@ -0,0 +1,144 @@
+//! The widest sets of locals which may be tainted in each block of the crate,
+//! for reviewing by hand that nothing dangerous is possible.
+
+use std::{cell::RefCell, rc::Rc};
+
+use rustc_hir::def::DefKind;
+use rustc_index::bit_set::BitSet;
+use rustc_middle::mir::{Body, Local, Location, VarDebugInfoContents};
+use rustc_middle::ty::TyCtxt;
+
+use crate::analysis_config::{RecursionAssumption, UnknownCallPolicy};
+use crate::attributes::AttrInfo;
+use crate::eval::config::TaintConfig;
+use crate::report::MaxTaint;
+use crate::taint_analysis::{Shared, TaintAnalysis};
+
+/// Analyze every function and closure of the crate with all of its arguments tainted,
+/// and the most conservative options: calls without a body return tainted values and write through their arguments,
+/// recursive calls return tainted values, and implicit flows, reads through raw pointers and lengths are tainted.
+/// Annotated functions are skipped, since their behavior is already known, and no violations are reported.
+pub fn max_taint(tcx: TyCtxt<'_>, info: &AttrInfo, config: &TaintConfig) -> MaxTaint {
+    let config = &TaintConfig {
+        unknown_call_policy: UnknownCallPolicy::TaintReturn,
+        recursion: RecursionAssumption::Pessimistic,
+        mutate_args_on_call: true,
+        unsafe_conservative: true,
+        implicit_flows: true,
+        taint_len: true,
+        clean_comparisons: false,
+        sanitizer_boundaries: Vec::new(),
+        max_call_depth: None,
+        ..config.clone()
+    };
+
+    let mut result = MaxTaint::default();
+    for local_id in tcx.body_owners() {
+        let id = local_id.to_def_id();
+        if !(matches!(tcx.def_kind(id), DefKind::Fn | DefKind::AssocFn) || tcx.is_closure(id))
+            || info.get_kind(&id).is_some()
+            || config.is_ignored(&tcx.def_path_str(id))
+        {
+            continue;
+        }
+
+        let shared = Rc::new(RefCell::new(Shared {
+            silent: true,
+            ..Shared::default()
+        }));
+        let body = tcx.optimized_mir(id);
+        let init = vec![Some(true); body.arg_count];
+        let mut results = TaintAnalysis::new_with_init(tcx, info, config, body, shared, init)
+            .into_results()
+            .into_results_cursor(body);
+
+        let mut blocks = Vec::new();
+        for (block, data) in body.basic_blocks().iter_enumerated() {
+            // The locals tainted anywhere in the block, including by its terminator.
+            results.seek_to_block_end(block);
+            let mut tainted = results.get().tainted.clone();
+            for statement_index in 0..=data.statements.len() {
+                results.seek_before_primary_effect(Location {
+                    block,
+                    statement_index,
+                });
+                tainted.union(&results.get().tainted);
+            }
+            blocks.push((format!("{:?}", block), local_names(body, &tainted)));
+        }
+        result.functions.insert(tcx.def_path_str(id), blocks);
+    }
+    result
+}
+
+/// The locals in `locals`, followed by the names of the variables they hold, if any, such as `_1 (value)`.
+fn local_names(body: &Body<'_>, locals: &BitSet<Local>) -> Vec<String> {
+    locals
+        .iter()
+        .map(|local| {
+            let name = body
+                .var_debug_info
+                .iter()
+                .find_map(|info| match info.value {
+                    VarDebugInfoContents::Place(place)
+                        if place.local == local && place.projection.is_empty() =>
+                    {
+                        Some(info.name)
+                    }
+                    _ => None,
+                });
+            match name {
+                Some(name) => format!("{:?} ({})", local, name),
+                None => format!("{:?}", local),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::config::TaintConfig;
+    use crate::test_utils::analyze;
+
+    #[test]
+    fn max_taint_of_function() {
+        let config = TaintConfig {
+            max_taint: true,
+            ..TaintConfig::default()
+        };
+        let report = analyze(
+            r#"
+fn main() {}
+
+fn review(value: i32, flag: bool) -> i32 {
+    let copy = value;
+    let length = unsafe { unknown(1) };
+    if flag {
+        copy
+    } else {
+        length
+    }
+}
+
+extern "Rust" {
+    fn unknown(_: i32) -> i32;
+}
+"#,
+            config,
+        );
+
+        // `length` is tainted from the start, since the unknown function may return a tainted value
+        // even though its argument is clean.
+        assert_eq!(
+            report.max_taint.unwrap().to_text(),
+            "main:\n    \
+                 bb0:\n\
+             review:\n    \
+                 bb0: _1 (value), _2 (flag), _3 (copy), _4 (length)\n    \
+                 bb1: _1 (value), _2 (flag), _3 (copy), _4 (length), _5\n    \
+                 bb2: _0, _1 (value), _2 (flag), _3 (copy), _4 (length), _5\n    \
+                 bb3: _0, _1 (value), _2 (flag), _3 (copy), _4 (length), _5\n    \
+                 bb4: _0, _1 (value), _2 (flag), _3 (copy), _4 (length), _5\n"
+        );
+    }
+}
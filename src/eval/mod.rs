@@ -1,4 +1,7 @@
 //! Logic for running the taint analysis
 
-pub mod attributes;
+pub mod config;
+pub mod config_file;
 pub mod main;
+pub mod max_taint;
+pub mod summary;
@@ -0,0 +1,197 @@
+//! Summaries of how taint flows through the public API of a crate.
+
+use std::{cell::RefCell, rc::Rc};
+
+use rustc_hir::def::DefKind;
+use rustc_middle::mir::Local;
+use rustc_middle::ty::{TyCtxt, Visibility};
+
+use crate::attributes::{AttrInfo, AttrInfoKind};
+use crate::eval::config::TaintConfig;
+use crate::report::{ApiSummary, FunctionSummary};
+use crate::taint_analysis::{InitSet, Shared, TaintAnalysis};
+use crate::violation::{INDEX_RULE, PANIC_RULE};
+
+/// Summarize every public function of the crate.
+/// Annotated functions are only listed with their kind, since their behavior is already known.
+pub fn summarize_api(tcx: TyCtxt<'_>, info: &AttrInfo, config: &TaintConfig) -> ApiSummary {
+    let mut functions = Vec::new();
+
+    // Panics are always checked, since they are part of the summary.
+    let config = &TaintConfig {
+        panic_on_tainted_is_sink: true,
+        tainted_index_is_sink: true,
+        ..config.clone()
+    };
+
+    for local_id in tcx.body_owners() {
+        let id = local_id.to_def_id();
+
+        if !matches!(tcx.def_kind(id), DefKind::Fn | DefKind::AssocFn)
+            || tcx.visibility(id) != Visibility::Public
+            || config.is_ignored(&tcx.def_path_str(id))
+        {
+            continue;
+        }
+
+        let mut summary = FunctionSummary {
+            function: tcx.def_path_str(id),
+            ..FunctionSummary::default()
+        };
+
+        if let Some(kind) = info.get_kind(&id) {
+            summary.kind = Some(
+                match kind {
+                    AttrInfoKind::Source => "source",
+                    AttrInfoKind::Sink => "sink",
+                    AttrInfoKind::Sanitizer => "sanitizer",
+                }
+                .to_owned(),
+            );
+            functions.push(summary);
+            continue;
+        }
+
+        let arg_count = tcx.optimized_mir(id).arg_count;
+        let analyze = |init: InitSet| {
+            let shared = Rc::new(RefCell::new(Shared {
+                silent: true,
+                ..Shared::default()
+            }));
+            let body = tcx.optimized_mir(id);
+            let exit_state =
+                TaintAnalysis::new_with_init(tcx, info, config, body, shared.clone(), init)
+                    .into_exit_state();
+
+            let returns_tainted =
+                exit_state.map_or(false, |state| state.contains(Local::from_usize(0)));
+            let shared = shared.borrow();
+            (
+                returns_tainted,
+                shared.tainted_panics > 0,
+                shared
+                    .violations
+                    .iter()
+                    .any(|v| v.rule != PANIC_RULE && v.rule != INDEX_RULE),
+            )
+        };
+
+        summary.returns_tainted = analyze(vec![Some(false); arg_count]).0;
+
+        // Taint one argument at a time, to find where each of them flows.
+        for arg in 0..arg_count {
+            let mut init = vec![Some(false); arg_count];
+            init[arg] = Some(true);
+
+            let (returns_tainted, panics, reaches_sink) = analyze(init);
+            if returns_tainted && !summary.returns_tainted {
+                summary.arg_to_return.push(arg);
+            }
+            if panics {
+                summary.arg_to_panic.push(arg);
+            }
+            if reaches_sink {
+                summary.arg_to_sink.push(arg);
+            }
+        }
+
+        functions.push(summary);
+    }
+
+    ApiSummary { functions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::analyze;
+
+    const PROGRAM: &str = r#"
+fn main() {}
+
+pub fn first(a: i32, _b: i32) -> i32 {
+    a
+}
+
+pub fn second(_a: i32, b: i32) -> i32 {
+    b
+}
+
+pub fn parse(text: Option<i32>) -> i32 {
+    text.unwrap()
+}
+
+pub fn log(message: i32) {
+    output(message);
+}
+
+pub fn fetch() -> i32 {
+    input()
+}
+
+fn private(a: i32) -> i32 {
+    a
+}
+
+#[taint::source]
+pub fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
+"#;
+
+    #[test]
+    fn summarize_public_functions() {
+        let config = TaintConfig {
+            export_summary: Some("summary.json".to_owned()),
+            ..TaintConfig::default()
+        };
+        let api = analyze(PROGRAM, config).api.unwrap();
+
+        let names = api
+            .functions
+            .iter()
+            .map(|f| f.function.as_str())
+            .collect::<Vec<_>>();
+        assert!(!names.contains(&"private"));
+
+        let get = |name: &str| {
+            api.functions
+                .iter()
+                .find(|f| f.function == name)
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(get("first").arg_to_return, vec![0]);
+        assert_eq!(get("log").arg_to_sink, vec![0]);
+        assert!(get("fetch").returns_tainted);
+        assert_eq!(get("input").kind.as_deref(), Some("source"));
+
+        assert_eq!(
+            get("first").to_json(),
+            "{\"function\":\"first\",\"kind\":null,\"returns_tainted\":false,\"arg_to_return\":[0],\"arg_to_panic\":[],\"arg_to_sink\":[]}"
+        );
+    }
+
+    #[test]
+    fn api_table() {
+        let config = TaintConfig {
+            api_table: true,
+            ..TaintConfig::default()
+        };
+        let mut api = analyze(PROGRAM, config).api.unwrap();
+        api.functions
+            .retain(|f| ["second", "parse", "log"].contains(&f.function.as_str()));
+        api.functions.sort_by(|a, b| a.function.cmp(&b.function));
+
+        assert_eq!(
+            api.to_table(),
+            "function | kind | return | panic | sink\n\
+             log      |      |        |       | arg0\n\
+             parse    |      | arg0   | arg0  |\n\
+             second   |      | arg1   |       |\n"
+        );
+    }
+}
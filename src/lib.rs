@@ -1,3 +1,14 @@
+//! A taint analysis on MIR.
+//!
+//! The dataflow analysis itself is in `taint_analysis`, over the domain in `taint_domain`, and can be extended through `rvalue_model`.
+//! It reads its options through `analysis_config`, finds sources and sinks with `attributes`, and records what it finds as `violation`s.
+//! The rest is only built with the `eval` feature: `eval` runs the analysis over a crate, `report` describes its results,
+//! `reporter` writes them out, and `query` answers questions about them.
+//! `rules` lists how the analysis propagates taint, for documentation,
+//! and `expectations` compares its results with the ones annotated in the analyzed program.
+//! The `taint` driver, which runs the analysis from the command line and writes reports to disk,
+//! is only built with the `driver` feature, which enables `eval` and is enabled by default.
+
 #![feature(rustc_private)]
 #![feature(box_syntax)]
 #![feature(box_patterns)]
@@ -6,6 +17,7 @@ extern crate rustc_apfloat;
 extern crate rustc_ast;
 extern crate rustc_data_structures;
 extern crate rustc_errors;
+extern crate rustc_feature;
 extern crate rustc_hir;
 extern crate rustc_index;
 extern crate rustc_interface;
@@ -18,6 +30,7 @@ extern crate rustc_target;
 
 mod analysis;
 
+#[cfg(feature = "eval")]
 pub mod eval;
 
 pub use analysis::*;
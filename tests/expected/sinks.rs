@@ -0,0 +1,33 @@
+// Every sink which receives a tainted value is reported, and those which only receive clean values are not.
+fn main() {
+    let name = input(); //~ TAINT source
+    let query = Query::new();
+    query.execute(name); //~ VIOLATION sink=Query::execute
+    query.execute(2);
+
+    log(name); //~ VIOLATION sink=log
+    log(3);
+    exec(4);
+}
+
+struct Query;
+
+impl Query {
+    fn new() -> Self {
+        Query
+    }
+
+    #[taint::sink]
+    fn execute(&self, _: i32) {}
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn log(_: i32) {}
+
+#[taint::sink]
+fn exec(_: i32) {}
@@ -0,0 +1,30 @@
+// Tainted values reach sinks directly, through other functions, and through references.
+// Within a function, the taint of an argument comes from the argument,
+// and taint written through a reference does not keep where it came from.
+fn main() {
+    let value = input(); //~ TAINT source
+    output(value); //~ VIOLATION sink=output
+    forward(value);
+
+    let mut slot = 0;
+    store(&mut slot);
+    output(slot); //~ VIOLATION sink=output
+
+    output(1);
+}
+
+fn forward(value: i32) { //~ TAINT source
+    output(value + 1); //~ VIOLATION sink=output
+}
+
+fn store(slot: &mut i32) {
+    *slot = input();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
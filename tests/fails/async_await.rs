@@ -0,0 +1,66 @@
+// Test that awaiting a future which produces tainted data taints the result,
+// across the `.await` points of an async function.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::future::Future;
+use std::ptr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+async fn fetch() -> i32 {
+    input()
+}
+
+async fn double(value: i32) -> i32 {
+    value * 2
+}
+
+async fn constant() -> i32 {
+    1
+}
+
+async fn run() {
+    let fetched = fetch().await;
+    output(fetched); //~ ERROR function `output` received tainted input [T0001]
+
+    let doubled = double(fetched).await;
+    output(doubled); //~ ERROR function `output` received tainted input [T0001]
+}
+
+async fn run_clean() {
+    output(constant().await);
+    output(double(1).await);
+}
+
+fn main() {
+    block_on(run());
+    block_on(run_clean());
+}
+
+/// Polls `future` until it completes, which the futures above do without waiting.
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn noop(_: *const ()) {}
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    let waker = unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
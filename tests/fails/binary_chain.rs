@@ -0,0 +1,23 @@
+// Test that the result of an expression with several operators is tainted if any of its leaves is,
+// through the temporaries which hold the intermediate results.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let a = 5;
+    let tainted = input();
+    output(a * 2 + tainted); //~ ERROR function `output` received tainted input [T0001]
+    output(tainted * 2 + a); //~ ERROR function `output` received tainted input [T0001]
+    output((a + 1) * (3 - tainted) / 2); //~ ERROR function `output` received tainted input [T0001]
+    output(a * 2 + 3);
+    output((a + 1) * (3 - a) / 2);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    4
+}
+
+#[taint::sink]
+fn output(_: i32) {}
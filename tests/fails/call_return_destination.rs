@@ -0,0 +1,29 @@
+// Test that the value returned by a call taints its destination, through nested calls,
+// and that a destination overwritten by a clean result is clean again.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let mut value = input();
+    let result = middle(value);
+    output(result); //~ ERROR function `output` received tainted input [T0001]
+    value = middle(0);
+    output(value);
+}
+
+fn inner(x: i32) -> i32 {
+    x
+}
+
+fn middle(x: i32) -> i32 {
+    inner(x)
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
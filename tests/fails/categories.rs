@@ -0,0 +1,42 @@
+// Test that sinks with categories only report sources of the same categories, or without any.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    query(read_file()); // A file is not expected to hold SQL.
+    query(read_request()); //~ ERROR function `query` received tainted input [T0001]
+    query(read_anything()); //~ ERROR function `query` received tainted input [T0001]
+    write_file(read_request()); //~ ERROR function `write_file` received tainted input [T0001]
+    log(read_file()); //~ ERROR function `log` received tainted input [T0001]
+}
+
+#[taint::source(filesystem)]
+fn read_file() -> String {
+    String::new()
+}
+
+#[taint::source(sql, network)]
+fn read_request() -> String {
+    String::new()
+}
+
+#[taint::source]
+fn read_anything() -> String {
+    String::new()
+}
+
+#[taint::sink(sql)]
+fn query(_: String) {
+    ()
+}
+
+#[taint::sink(filesystem, network)]
+fn write_file(_: String) {
+    ()
+}
+
+#[taint::sink]
+fn log(_: String) {
+    ()
+}
@@ -0,0 +1,30 @@
+// Test that values received from a channel in `main` are tainted
+// if a tainted value was sent through it by a spawned closure, which is analyzed after `main`.
+// compile-flags: --taint-channels
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::sync::mpsc;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        sender.send(input()).unwrap();
+    });
+    handle.join().unwrap();
+
+    let received = receiver.recv().unwrap();
+    output(received); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,34 @@
+// Test that values received from a channel are tainted if a tainted value was sent through it,
+// when `--taint-channels` is set.
+// compile-flags: --taint-channels
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::sync::mpsc;
+use std::thread;
+
+fn main() {
+    let (sender, receiver) = mpsc::channel();
+    sender.send(input()).unwrap();
+
+    let handle = thread::spawn(move || {
+        let received = receiver.recv().unwrap();
+        output(received); //~ ERROR function `output` received tainted input [T0001]
+    });
+    handle.join().unwrap();
+
+    let (clean_sender, clean_receiver) = mpsc::channel();
+    clean_sender.send(String::new()).unwrap();
+    output(clean_receiver.recv().unwrap().len() as i32);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
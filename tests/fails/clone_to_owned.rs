@@ -0,0 +1,34 @@
+// Test that `clone` and `to_owned` pass the taint of their receiver to their result,
+// for the standard library's types and for derived implementations.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+#[derive(Clone)]
+struct Request {
+    body: String,
+}
+
+fn main() {
+    let secret = input();
+    output(secret.clone()); //~ ERROR function `output` received tainted input [T0001]
+
+    let slice: &str = &secret;
+    output(slice.to_owned()); //~ ERROR function `output` received tainted input [T0001]
+
+    let request = Request { body: input() };
+    let copy = request.clone();
+    output(copy.body); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean = String::new();
+    output(clean.clone());
+}
+
+#[taint::source]
+fn input() -> String {
+    String::new()
+}
+
+#[taint::sink]
+fn output(_: String) {}
@@ -0,0 +1,28 @@
+// Test that closures passed to generic functions are analyzed with the taint of the arguments
+// those functions call them with, whether in the standard library or through a reference.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let _ = Some(1).map(|x| output(x + secret)); //~ ERROR function `output` received tainted input [T0001]
+    apply(|x| output(x), secret); //~ ERROR function `output` received tainted input [T0001]
+    apply(|x| output(x), 1);
+}
+
+fn apply<F: Fn(i32)>(f: F, value: i32) {
+    call(&f, value)
+}
+
+fn call<F: Fn(i32)>(f: &F, value: i32) {
+    f(value)
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,21 @@
+// Test that a closure is analyzed with the tainted arguments it is passed by a closure which is defined,
+// and so analyzed, after it.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let sink = |value: i32| output(value); //~ ERROR function `output` received tainted input [T0001]
+    let run = move || sink(input());
+    run();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
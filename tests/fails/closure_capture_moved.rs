@@ -0,0 +1,25 @@
+// Test that a closure with a tainted capture stays tainted when it is moved to another local.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let f = move || secret + 1;
+    let g = f;
+    output(apply(g)); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn apply<F: Fn() -> i32>(f: F) -> i32 {
+    f()
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
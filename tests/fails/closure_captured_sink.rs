@@ -0,0 +1,21 @@
+// Test that sinks inside the body of a closure are checked,
+// and that a tainted value captured by the closure is tainted within its body.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let val = input();
+    let print = || output(val); //~ ERROR function `output` received tainted input [T0001]
+    print();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
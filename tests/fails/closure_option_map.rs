@@ -0,0 +1,20 @@
+// Test that a sink in a closure passed to `Option::map` is checked against the closure's tainted captures.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let _ = Some(1).map(|x| output(x + secret)); //~ ERROR function `output` received tainted input [T0001]
+    let _ = Some(1).map(|x| output(x + 1));
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
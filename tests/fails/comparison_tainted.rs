@@ -0,0 +1,21 @@
+// Test that comparing a tainted value taints the result by default.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let x = 4;
+    output(x == secret); //~ ERROR function `output` received tainted input [T0001]
+    output(secret < x); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: bool) {
+    ()
+}
@@ -0,0 +1,25 @@
+// Test that a source given by `--taint-source` can taint a buffer passed to it,
+// without tainting its return value.
+// compile-flags: --taint-source=std::io::Stdin::read_line(1)
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::io;
+
+fn main() {
+    let mut buf = String::new();
+    let n = io::stdin().read_line(&mut buf).unwrap();
+    output_len(n);
+    output(buf); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::sink]
+fn output_len(_: usize) {
+    ()
+}
+
+#[taint::sink]
+fn output(_: String) {
+    ()
+}
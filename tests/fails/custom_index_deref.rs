@@ -0,0 +1,66 @@
+// Test that indexing and dereferencing user types, which call `Index::index` and `Deref::deref`
+// rather than projecting, pass the taint of the container to the result.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+struct Table {
+    rows: [i32; 4],
+}
+
+impl Index<usize> for Table {
+    type Output = i32;
+
+    fn index(&self, row: usize) -> &i32 {
+        &self.rows[row]
+    }
+}
+
+impl IndexMut<usize> for Table {
+    fn index_mut(&mut self, row: usize) -> &mut i32 {
+        &mut self.rows[row]
+    }
+}
+
+struct Wrapper(i32);
+
+impl Deref for Wrapper {
+    type Target = i32;
+
+    fn deref(&self) -> &i32 {
+        &self.0
+    }
+}
+
+impl DerefMut for Wrapper {
+    fn deref_mut(&mut self) -> &mut i32 {
+        &mut self.0
+    }
+}
+
+fn main() {
+    let mut table = Table {
+        rows: [input(), 2, 3, 4],
+    };
+    output(table[1]); //~ ERROR function `output` received tainted input [T0001]
+    output(*(&mut table[1])); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut wrapper = Wrapper(input());
+    output(*wrapper); //~ ERROR function `output` received tainted input [T0001]
+    output(*(&mut *wrapper)); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean = Table { rows: [1, 2, 3, 4] };
+    output(clean[0]);
+    output(*Wrapper(1));
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,24 @@
+// Test that derived implementations of `Clone` copy the taint of their receiver.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+#[derive(Clone)]
+struct Copied(i32);
+
+fn main() {
+    let copied = Copied(input()).clone();
+    output(copied.0); //~ ERROR function `output` received tainted input [T0001]
+
+    let text = input().to_string();
+    output(text.len() as i32); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    0
+}
+
+#[taint::sink]
+fn output(_: i32) {}
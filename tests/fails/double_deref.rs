@@ -0,0 +1,29 @@
+// Test that taint is tracked through references to references,
+// both when reading a tainted value and when writing one.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let val = input();
+    let r = &val;
+    let rr = &r;
+    output(**rr); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut buffer = 0;
+    let mut w = &mut buffer;
+    let ww = &mut w;
+    **ww = input();
+    output(buffer); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
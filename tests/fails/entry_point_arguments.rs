@@ -0,0 +1,25 @@
+// Test that the arguments of an entry point are tainted,
+// so that sinks reached from them are reported.
+// compile-flags: --taint-entry-point=handle_request
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    handle_request(1, 2);
+}
+
+fn handle_request(id: i32, length: i32) {
+    let total = id + length;
+    store(total);
+    output(length); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn store(value: i32) {
+    output(value); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
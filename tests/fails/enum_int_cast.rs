@@ -0,0 +1,34 @@
+// Test that casting a tainted enum or number to another numeric type preserves taint.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+#[derive(Clone, Copy)]
+enum Level {
+    Low = 1,
+    High = 10,
+}
+
+fn main() {
+    let level = input();
+    let as_int = level as i32;
+    output(as_int); //~ ERROR function `output` received tainted input [T0001]
+
+    let widened = as_int as i64;
+    output(widened as i32); //~ ERROR function `output` received tainted input [T0001]
+
+    let float = as_int as f64 * 1.5;
+    output(float as i32); //~ ERROR function `output` received tainted input [T0001]
+
+    output(Level::Low as i32);
+}
+
+#[taint::source]
+fn input() -> Level {
+    Level::High
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
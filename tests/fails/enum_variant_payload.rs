@@ -0,0 +1,46 @@
+// Test that an enum built with a tainted payload is tainted, along with the payload matched out of it,
+// while the variants built from clean data are not.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+enum Message {
+    Text(i32),
+    Pair { left: i32, right: i32 },
+    Empty,
+}
+
+fn payload(message: Message) -> i32 {
+    match message {
+        Message::Text(value) => value,
+        Message::Pair { left, right } => left + right,
+        Message::Empty => 0,
+    }
+}
+
+fn main() {
+    let message = Message::Text(input());
+    if let Message::Text(value) = message {
+        output(value); //~ ERROR function `output` received tainted input [T0001]
+    }
+
+    let message = Message::Pair { left: 1, right: input() };
+    output(payload(message)); //~ ERROR function `output` received tainted input [T0001]
+
+    let message = Some(input());
+    match message {
+        Some(value) => output(value), //~ ERROR function `output` received tainted input [T0001]
+        None => {}
+    }
+
+    output(payload(Message::Text(1)));
+    output(payload(Message::Empty));
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,33 @@
+// Test that a reference to a field carries the taint of the whole struct,
+// since fields are not tracked separately.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+struct Request {
+    body: i32,
+    id: i32,
+}
+
+fn main() {
+    let tainted = Request {
+        body: input(),
+        id: 0,
+    };
+    let clean = Request { body: 1, id: 2 };
+
+    output(&tainted.body); //~ ERROR function `output` received tainted input [T0001]
+    output(&tainted.id); //~ ERROR function `output` received tainted input [T0001]
+    output(&clean.body);
+    output(&clean.id);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: &i32) {
+    ()
+}
@@ -0,0 +1,31 @@
+// Test that `--taint-flow` rules replace the analysis of the functions they describe.
+// compile-flags: --taint-flow=pick(arg1->ret) --taint-flow=copy_into(arg0->*arg1)
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    output(pick(secret, 0));
+    output(pick(0, secret)); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut copy = 0;
+    copy_into(&secret, &mut copy);
+    output(copy); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn pick(a: i32, _: i32) -> i32 {
+    a
+}
+
+fn copy_into(_: &i32, _: &mut i32) {}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,31 @@
+// Test that formatting a tainted value, which `format!` and `{:?}` take by reference,
+// or a reference to one, taints the formatted string.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+#[derive(Debug)]
+struct Request {
+    body: String,
+}
+
+fn main() {
+    let request = Request { body: input() };
+    output(format!("{:?}", &request)); //~ ERROR function `output` received tainted input [T0001]
+    output(format!("{:?}", request)); //~ ERROR function `output` received tainted input [T0001]
+
+    let body = &request.body;
+    output(format!("{}", &body)); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean = Request { body: String::new() };
+    output(format!("{:?}", &clean));
+}
+
+#[taint::source]
+fn input() -> String {
+    String::new()
+}
+
+#[taint::sink]
+fn output(_: String) {}
@@ -0,0 +1,45 @@
+// Test that associated functions of generic impls, including impls with const generics,
+// can be sinks, whether they are called directly or through a trait.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+struct Wrapper<T> {
+    inner: T,
+}
+
+impl<T> Wrapper<T> {
+    #[taint::sink]
+    fn exec(&self, _: T) {}
+}
+
+trait Runner<T> {
+    fn run(&self, value: T);
+}
+
+impl<T> Runner<T> for Wrapper<T> {
+    #[taint::sink]
+    fn run(&self, _: T) {}
+}
+
+struct Buffer<const N: usize>;
+
+impl<const N: usize> Buffer<N> {
+    #[taint::sink]
+    fn write(&self, _: i32) {}
+}
+
+fn main() {
+    let wrapper = Wrapper { inner: 0 };
+    wrapper.exec(input()); //~ ERROR function `Wrapper::<i32>::exec` received tainted input [T0001]
+    wrapper.run(input()); //~ ERROR function `<Wrapper<i32> as Runner<i32>>::run` received tainted input [T0001]
+    wrapper.exec(wrapper.inner);
+
+    let buffer = Buffer::<4>;
+    buffer.write(input()); //~ ERROR function `Buffer::<4_usize>::write` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
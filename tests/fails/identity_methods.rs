@@ -0,0 +1,34 @@
+// Test that `as_ref`, `as_mut` and `borrow` pass the taint of their receiver to their result.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::borrow::Borrow;
+
+fn main() {
+    let secret = input();
+    output(secret.as_ref()); //~ ERROR function `output::<std::option::Option<&std::string::String>>` received tainted input [T0001]
+
+    let text = secret.unwrap_or_default();
+    let path: &std::path::Path = text.as_ref();
+    output(path); //~ ERROR function `output::<&std::path::Path>` received tainted input [T0001]
+    let borrowed: &str = text.borrow();
+    output(borrowed); //~ ERROR function `output::<&str>` received tainted input [T0001]
+
+    let mut copy = input();
+    output(copy.as_mut()); //~ ERROR function `output::<std::option::Option<&mut std::string::String>>` received tainted input [T0001]
+
+    let clean = Some(String::new());
+    output(clean.as_ref());
+}
+
+#[taint::source]
+fn input() -> Option<String> {
+    Some(String::new())
+}
+
+#[taint::sink]
+fn output<T>(_: T) {
+    ()
+}
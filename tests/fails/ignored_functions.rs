@@ -0,0 +1,28 @@
+// Test that functions matching `--taint-ignore` are not analyzed,
+// and that calls to them still propagate the taint of their arguments.
+// compile-flags: --taint-ignore=generated::*
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+mod generated {
+    pub fn encode(value: i32) -> i32 {
+        super::output(value);
+        value * 2
+    }
+}
+
+fn main() {
+    let encoded = generated::encode(input());
+    output(encoded); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,34 @@
+// Test that converting a tainted boolean to an integer through a branch
+// leaks the condition when `--taint-implicit-flows` is set.
+// compile-flags: --taint-implicit-flows
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let bit = if secret { 1 } else { 0 };
+    output(bit); //~ ERROR function `output` received tainted input [T0001]
+
+    // The constants are assigned after other calls in the branches.
+    let logged = if secret {
+        log();
+        1
+    } else {
+        log();
+        0
+    };
+    output(logged); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn log() {}
+
+#[taint::source]
+fn input() -> bool {
+    true
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
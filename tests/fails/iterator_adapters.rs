@@ -0,0 +1,30 @@
+// Test that iterator adapters and `collect` carry the taint of the iterator and of the closures passed to them.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let values = input();
+    let doubled: Vec<i32> = values.iter().map(|x| x * 2).collect();
+    output(doubled); //~ ERROR function `output` received tainted input [T0001]
+
+    let evens: Vec<i32> = input().into_iter().filter(|x| x % 2 == 0).collect();
+    output(evens); //~ ERROR function `output` received tainted input [T0001]
+
+    let offset = input()[0];
+    let shifted: Vec<i32> = vec![1, 2].into_iter().map(|x| x + offset).collect();
+    output(shifted); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean: Vec<i32> = vec![1, 2].iter().map(|x| x * 2).collect();
+    output(clean);
+}
+
+#[taint::source]
+fn input() -> Vec<i32> {
+    vec![15]
+}
+
+#[taint::sink]
+fn output(_: Vec<i32>) {
+    ()
+}
@@ -0,0 +1,37 @@
+// Test that a struct too large to be returned in registers carries the taint of the values it is built from.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+struct Large {
+    padding: [i64; 32],
+    value: i64,
+}
+
+fn wrap(value: i64) -> Large {
+    Large {
+        padding: [0; 32],
+        value,
+    }
+}
+
+fn main() {
+    let secret = input();
+    let large = wrap(secret);
+    output(large.value + large.padding[0]); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut boxed = Box::new(wrap(0));
+    output(boxed.value + boxed.padding[0]);
+    *boxed = wrap(secret);
+    output(boxed.value + boxed.padding[0]); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i64 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i64) {
+    ()
+}
@@ -0,0 +1,33 @@
+// Test that a value inserted into a `HashMap` or `BTreeMap` taints the whole map,
+// so that the values read from it with any key are tainted.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::collections::{BTreeMap, HashMap};
+
+fn main() {
+    let mut map = HashMap::new();
+    map.insert("secret", input());
+    map.insert("public", 1);
+    output(*map.get("public").unwrap()); //~ ERROR function `output` received tainted input [T0001]
+    output(map["secret"]); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut tree = BTreeMap::new();
+    tree.insert(input(), 1);
+    for value in tree.values() {
+        output(*value); //~ ERROR function `output` received tainted input [T0001]
+    }
+
+    let mut clean = HashMap::new();
+    clean.insert("public", 1);
+    output(clean["public"]);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,32 @@
+// Test that the result of `matches!` on a tainted value is tainted,
+// since it reveals which variant the value is.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+enum Command {
+    Read,
+    Write(i32),
+}
+
+fn main() {
+    let command = input(false);
+    let is_write = matches!(command, Command::Write(_));
+    output(is_write); //~ ERROR function `output` received tainted input [T0001]
+    let is_large = matches!(command, Command::Write(n) if n > 3);
+    output(is_large); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input(read: bool) -> Command {
+    if read {
+        Command::Read
+    } else {
+        Command::Write(15)
+    }
+}
+
+#[taint::sink]
+fn output(_: bool) {
+    ()
+}
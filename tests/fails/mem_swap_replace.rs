@@ -0,0 +1,38 @@
+// Test that `mem::swap` exchanges the taint of its arguments,
+// and that `mem::replace` and `mem::take` return the old taint of their destination and install the new.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::mem;
+
+fn main() {
+    let mut tainted = input();
+    let mut clean = 0;
+    mem::swap(&mut tainted, &mut clean);
+    output(tainted);
+    output(clean); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut slot = 0;
+    let old = mem::replace(&mut slot, input());
+    output(old);
+    output(slot); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut slot = input();
+    let old = mem::replace(&mut slot, 0);
+    output(old); //~ ERROR function `output` received tainted input [T0001]
+    output(slot);
+
+    let mut slot = input();
+    let old = mem::take(&mut slot);
+    output(old); //~ ERROR function `output` received tainted input [T0001]
+    output(slot);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,41 @@
+// Test that a local assigned a tainted value on one branch and a constant on the other
+// is tainted after the branches merge.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    output(pick(true)); //~ ERROR function `output` received tainted input [T0001]
+    output(pick_clean(true));
+}
+
+fn pick(flag: bool) -> i32 {
+    let x;
+    if flag {
+        x = input();
+    } else {
+        x = 1;
+    }
+    x
+}
+
+fn pick_clean(flag: bool) -> i32 {
+    let x;
+    if flag {
+        x = 2;
+    } else {
+        x = 1;
+    }
+    x
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
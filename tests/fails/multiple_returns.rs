@@ -0,0 +1,30 @@
+// Test that a function returns tainted data if any of its return paths does.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let value = choose(true);
+    output(value); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn choose(early: bool) -> i32 {
+    if early {
+        return 0;
+    }
+    let secret = input();
+    if secret > 10 {
+        return 1;
+    }
+    secret
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,30 @@
+// Test that a call to a function without MIR taints the referents of its reference arguments
+// if any argument is tainted, when `--taint-mutate-args-on-call` is set.
+// compile-flags: --taint-mutate-args-on-call
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+extern "C" {
+    fn fill(value: i32, buffer: &mut i32);
+}
+
+fn main() {
+    let mut clean = 0;
+    unsafe { fill(1, &mut clean) };
+    output(clean);
+
+    let mut buffer = 0;
+    unsafe { fill(input(), &mut buffer) };
+    output(buffer); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,27 @@
+// Test that a tainted value stays tainted when it is returned as an opaque type.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::fmt::Display;
+
+fn main() {
+    let secret = input();
+    let hidden = hide(secret);
+    output(hidden.to_string()); //~ ERROR function `output` received tainted input [T0001]
+    output(hide(0).to_string());
+}
+
+fn hide(value: i32) -> impl Display {
+    value
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: String) {
+    ()
+}
@@ -0,0 +1,19 @@
+// Test that printing a tainted value is reported with `--taint-output-sinks`.
+// compile-flags: --taint-output-sinks
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    println!("{}", secret); //~ ERROR function `std::io::_print` received tainted input [T0001]
+    eprintln!("value: {}", secret); //~ ERROR function `std::io::_eprint` received tainted input [T0001]
+
+    let clean = 1;
+    println!("{}", clean);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
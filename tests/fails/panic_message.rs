@@ -0,0 +1,24 @@
+// Test that formatting a tainted value into a panic message is reported
+// when `--taint-panic-message-is-sink` is set.
+// compile-flags: --taint-panic-message-is-sink
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    if secret > 100 {
+        panic!("invalid value {}", secret); //~ ERROR tainted value formatted into a panic message [T0007]
+    }
+    if secret > 50 {
+        panic!("invalid value {}", 50);
+    }
+    if secret > 20 {
+        std::panic::panic_any(secret.to_string()); //~ ERROR tainted value formatted into a panic message [T0007]
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
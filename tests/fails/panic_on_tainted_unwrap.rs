@@ -0,0 +1,18 @@
+// Test that unwrapping a tainted value is reported when `--taint-panic-on-tainted-is-sink` is set.
+// compile-flags: --taint-panic-on-tainted-is-sink
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let val = input();
+    let _ = val.unwrap(); //~ ERROR `std::option::Option::<i32>::unwrap` may panic on tainted input [T0004]
+
+    let clean = Some(15);
+    let _ = clean.unwrap();
+}
+
+#[taint::source]
+fn input() -> Option<i32> {
+    Some(15)
+}
@@ -0,0 +1,28 @@
+// Test that casts between pointers and integers preserve taint in both directions.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let address = input();
+    let pointer = address as *const u8;
+    output_pointer(pointer); //~ ERROR function `output_pointer` received tainted input [T0001]
+
+    let back = pointer as usize;
+    output(back); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> usize {
+    0x1000
+}
+
+#[taint::sink]
+fn output(_: usize) {
+    ()
+}
+
+#[taint::sink]
+fn output_pointer(_: *const u8) {
+    ()
+}
@@ -0,0 +1,25 @@
+// Test that all public functions are treated as entry points
+// when `--taint-public-entry-points` is set.
+// compile-flags: --taint-public-entry-points
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    private_helper(0);
+}
+
+pub fn resize(length: usize) -> usize {
+    let doubled = length * 2;
+    output(doubled); //~ ERROR function `output` received tainted input [T0001]
+    doubled
+}
+
+fn private_helper(value: usize) {
+    output(value);
+}
+
+#[taint::sink]
+fn output(_: usize) {
+    ()
+}
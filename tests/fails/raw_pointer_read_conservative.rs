@@ -0,0 +1,19 @@
+// Test that values read through raw pointers are tainted
+// when `--taint-unsafe-conservative` is set.
+// compile-flags: --taint-unsafe-conservative
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let value = 4;
+    let pointer = &value as *const i32;
+    let read = unsafe { *pointer };
+    output(read); //~ ERROR function `output` received tainted input [T0001]
+    output(value);
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
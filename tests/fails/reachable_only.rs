@@ -0,0 +1,29 @@
+// Test that with `--taint-reachable-only`, closures which may not run when `main` is called are not analyzed,
+// while those which `main` calls or passes along are.
+// compile-flags: --taint-reachable-only
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let log = |value: i32| output(value + input()); //~ ERROR function `output` received tainted input [T0001]
+    apply(log);
+}
+
+fn apply(f: impl Fn(i32)) {
+    f(1);
+}
+
+#[allow(dead_code)]
+fn unused() {
+    let log = || output(input());
+    log();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
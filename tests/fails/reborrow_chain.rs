@@ -0,0 +1,42 @@
+// Test that a reborrowed reference refers to the same tainted value as the reference it was reborrowed from,
+// both for reads and for writes through the last reference in a chain.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let mut secret = input();
+    let r1 = &mut secret;
+    let r2 = &mut *r1;
+    let r3 = &*r2;
+    output(*r3); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut value = 1;
+    let w1 = &mut value;
+    let w2 = &mut *w1;
+    let w3 = &mut *w2;
+    *w3 = input();
+    output(value); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut written = 1;
+    write(&mut written);
+    output(written); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut clean = 1;
+    let c1 = &mut clean;
+    let c2 = &mut *c1;
+    output(*c2);
+}
+
+fn write(r1: &mut i32) {
+    let r2 = &mut *r1;
+    *r2 = input();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
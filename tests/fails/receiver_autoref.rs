@@ -0,0 +1,61 @@
+// Test that the taint of a receiver which is borrowed for a method call, such as `value.method()`
+// for a method taking `&self` or `&mut self`, reaches the method through the reference.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+struct Request {
+    body: i32,
+}
+
+impl Request {
+    fn body(&self) -> i32 {
+        self.body
+    }
+
+    fn body_mut(&mut self) -> &mut i32 {
+        &mut self.body
+    }
+
+    fn header(&self) -> i32 {
+        0
+    }
+}
+
+struct Builder {
+    value: i32,
+}
+
+impl Builder {
+    fn with(&mut self, value: i32) -> &mut Self {
+        self.value = value;
+        self
+    }
+
+    fn build(&self) -> i32 {
+        self.value
+    }
+}
+
+fn main() {
+    let request = Request { body: input() };
+    output(request.body()); //~ ERROR function `output` received tainted input [T0001]
+    output(request.header());
+
+    let mut request = Request { body: input() };
+    output(*request.body_mut()); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut builder = Builder { value: 0 };
+    output(builder.with(input()).build()); //~ ERROR function `output` received tainted input [T0001]
+
+    let secret = Some(input());
+    output(*secret.as_ref().unwrap()); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,39 @@
+// Test that a reference to tainted data is tainted, such as one passed to a sink or returned by a function,
+// and that data written through a reference taints its referent.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let value = input();
+    let reference = &value;
+    output_ref(reference); //~ ERROR function `output_ref` received tainted input [T0001]
+
+    let borrowed = borrow(&value);
+    output(*borrowed); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut referent = 0;
+    let written = &mut referent;
+    *written = input();
+    output(referent); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn borrow(value: &i32) -> &i32 {
+    value
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
+
+#[taint::sink]
+fn output_ref(_: &i32) {
+    ()
+}
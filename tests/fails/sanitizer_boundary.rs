@@ -0,0 +1,51 @@
+// Test that calls from outside to the functions of a module or type configured as a sanitizer boundary return clean values,
+// while the functions within the boundary are analyzed as usual.
+// compile-flags: --taint-sanitizer-boundary=validate --taint-sanitizer-boundary=Email
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+mod validate {
+    pub fn number(value: i32) -> i32 {
+        checked(value)
+    }
+
+    fn checked(value: i32) -> i32 {
+        super::output(value); //~ ERROR function `output` received tainted input [T0001]
+        value
+    }
+}
+
+struct Email(i32);
+
+impl Email {
+    fn parse(value: i32) -> Email {
+        Email(value)
+    }
+}
+
+impl From<i32> for Email {
+    fn from(value: i32) -> Email {
+        Email(value)
+    }
+}
+
+fn unchecked(value: i32) -> i32 {
+    value
+}
+
+fn main() {
+    let secret = input();
+    output(validate::number(secret));
+    output(Email::parse(secret).0);
+    output(Email::from(secret).0);
+    output(unchecked(secret)); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,30 @@
+// Test that assigning to a place from an expression which reads it keeps or adds taint.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let mut x = 1;
+    x = x + input();
+    output(x); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut y = input();
+    let clean = 2;
+    y = y + clean;
+    output(y); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut z = 3;
+    z = z + clean;
+    output(z);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
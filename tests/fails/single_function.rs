@@ -0,0 +1,28 @@
+// Test that only the function given to `--taint-function` is analyzed, with its argument tainted,
+// so that the call in `main` is not reported.
+// compile-flags: --taint-function=helper
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    output(input());
+    helper(1);
+}
+
+fn helper(value: i32) {
+    let copy = twice(value);
+    output(copy); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn twice(value: i32) -> i32 {
+    value * 2
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,34 @@
+// Test that the elements and subslices which slice patterns bind, by constant index or by subslice,
+// carry the taint of the slice or array they are taken from.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let data = [input(), 2, 3, 4];
+    let slice: &[i32] = &data;
+
+    if let [first, rest @ ..] = slice {
+        output(*first); //~ ERROR function `output` received tainted input [T0001]
+        output(rest[0]); //~ ERROR function `output` received tainted input [T0001]
+    }
+    if let [_, middle @ .., _] = slice {
+        output(middle[1]); //~ ERROR function `output` received tainted input [T0001]
+    }
+
+    let [_, _, _, last] = data;
+    output(last); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean = [1, 2, 3];
+    if let [_, rest @ ..] = &clean[..] {
+        output(rest[0]);
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,33 @@
+// Test that dereferencing a smart pointer to tainted data is tainted, through the built-in dereference of a `Box`
+// and the `Deref::deref` of an `Rc` or `Arc`.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() {
+    let boxed = Box::new(input());
+    output(*boxed); //~ ERROR function `output` received tainted input [T0001]
+
+    let shared = Rc::new(input());
+    output(*shared); //~ ERROR function `output` received tainted input [T0001]
+
+    let atomic = Arc::new(input());
+    output(*atomic); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean = Rc::new(1);
+    output(*clean);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,35 @@
+// Test that a `Box`, `Rc` or `Arc` built from a tainted value is tainted,
+// and so is the value read by dereferencing it.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() {
+    let boxed = Box::new(input());
+    output(*boxed); //~ ERROR function `output` received tainted input [T0001]
+
+    let shared = Rc::new(input());
+    let copy = Rc::clone(&shared);
+    output(*copy); //~ ERROR function `output` received tainted input [T0001]
+
+    let atomic = Arc::new(input());
+    output(*atomic); //~ ERROR function `output` received tainted input [T0001]
+
+    let nested = Box::new(Rc::new(input()));
+    output(**nested); //~ ERROR function `output` received tainted input [T0001]
+
+    output(*Box::new(1));
+    output(*Rc::new(2));
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
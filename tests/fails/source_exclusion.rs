@@ -0,0 +1,20 @@
+// Test that a function excluded with `--taint-source=!<pattern>` is not a source,
+// even though it matches a wildcard source.
+// compile-flags: --taint-source=std::env::* --taint-source=!std::env::current_dir
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let home = std::env::var("HOME").unwrap();
+    output(home); //~ ERROR function `output` received tainted input [T0001]
+
+    let dir = std::env::current_dir().unwrap();
+    output_path(dir);
+}
+
+#[taint::sink]
+fn output(_: String) {}
+
+#[taint::sink]
+fn output_path(_: std::path::PathBuf) {}
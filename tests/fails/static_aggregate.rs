@@ -0,0 +1,33 @@
+// Test that a constant tuple is tainted when one of its elements refers to a tainted static.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+static mut SECRET: i32 = 0;
+static mut PUBLIC: i32 = 0;
+
+static SECRET_PAIR: (i32, &i32) = (1, unsafe { &SECRET });
+static PUBLIC_PAIR: (i32, &i32) = (1, unsafe { &PUBLIC });
+
+fn main() {
+    unsafe {
+        SECRET = input();
+        PUBLIC = 2;
+    }
+
+    let (_, secret) = SECRET_PAIR;
+    output(*secret); //~ ERROR function `output` received tainted input [T0001]
+
+    let (_, public) = PUBLIC_PAIR;
+    output(*public);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,38 @@
+// Test that a static written with a tainted value in one function
+// is tainted when it is read in another one, even if it is analyzed first.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+static mut LAST: i32 = 0;
+static mut COUNT: i32 = 0;
+
+fn main() {
+    report();
+    store(input());
+    report();
+}
+
+fn store(value: i32) {
+    unsafe {
+        LAST = value;
+        COUNT += 1;
+    }
+}
+
+fn report() {
+    unsafe {
+        output(COUNT);
+        output(LAST); //~ ERROR function `output` received tainted input [T0001]
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,36 @@
+// Test that converting a tainted string to its bytes, or bytes back to a string, keeps the taint.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let text = input();
+    output(text.as_bytes()); //~ ERROR function `output` received tainted input [T0001]
+
+    let slice: &str = &text;
+    output(slice.as_bytes()); //~ ERROR function `output` received tainted input [T0001]
+
+    let owned = slice.to_string();
+    output(owned.as_bytes()); //~ ERROR function `output` received tainted input [T0001]
+
+    let bytes = input().into_bytes();
+    output(&bytes); //~ ERROR function `output` received tainted input [T0001]
+
+    let decoded = String::from_utf8(bytes).unwrap();
+    output(decoded.as_bytes()); //~ ERROR function `output` received tainted input [T0001]
+
+    let borrowed = std::str::from_utf8(text.as_bytes()).unwrap();
+    output(borrowed.as_bytes()); //~ ERROR function `output` received tainted input [T0001]
+
+    let number = 5.to_string();
+    output(number.as_bytes());
+}
+
+#[taint::source]
+fn input() -> String {
+    String::new()
+}
+
+#[taint::sink]
+fn output(_: &[u8]) {}
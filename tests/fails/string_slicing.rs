@@ -0,0 +1,29 @@
+// Test that slicing a tainted string, which goes through the `Index` and `SliceIndex` implementations for ranges,
+// returns a tainted substring.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let text = input();
+    output(&text[1..4]); //~ ERROR function `output` received tainted input [T0001]
+
+    let slice: &str = &text;
+    output(&slice[..2]); //~ ERROR function `output` received tainted input [T0001]
+    output(slice.get(2..).unwrap()); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut owned = input();
+    output(owned.get_mut(1..=2).unwrap()); //~ ERROR function `output` received tainted input [T0001]
+
+    let clean = String::from("clean");
+    output(&clean[1..3]);
+}
+
+#[taint::source]
+fn input() -> String {
+    String::new()
+}
+
+#[taint::sink]
+fn output(_: &str) {}
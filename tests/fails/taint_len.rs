@@ -0,0 +1,25 @@
+// Test that the length of a tainted slice is tainted with `--taint-len`,
+// so that branching on it in a slice pattern taints the values assigned in its arms.
+// compile-flags: --taint-len --taint-implicit-flows
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let data = [input(), 2];
+    let slice: &[i32] = &data;
+    let count = match slice {
+        [_] => 1,
+        [_, _] => 2,
+        _ => 0,
+    };
+    output(count); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
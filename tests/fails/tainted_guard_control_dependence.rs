@@ -0,0 +1,26 @@
+// Test that a sink called anywhere in a branch taken on tainted data is reported,
+// even after other calls in the branch, and without `--taint-implicit-flows`,
+// but not once the branches have joined again.
+// compile-flags: --taint-tainted-guard-is-sink
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let flag = input();
+    if flag {
+        log();
+        output(42); //~ ERROR function `output` is called depending on tainted input [T0005]
+    }
+    output(7);
+}
+
+fn log() {}
+
+#[taint::source]
+fn input() -> bool {
+    true
+}
+
+#[taint::sink]
+fn output(_: i32) {}
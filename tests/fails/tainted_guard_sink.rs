@@ -0,0 +1,24 @@
+// Test that a sink called only when a tainted condition holds is reported,
+// even though its argument is clean, when `--taint-tainted-guard-is-sink` is set.
+// compile-flags: --taint-implicit-flows --taint-tainted-guard-is-sink
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let flag = input();
+    let secret = 42;
+    if flag {
+        output(secret); //~ ERROR function `output` is called depending on tainted input [T0005]
+    }
+}
+
+#[taint::source]
+fn input() -> bool {
+    true
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
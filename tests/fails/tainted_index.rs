@@ -0,0 +1,19 @@
+// Test that indexing with a tainted value is reported
+// when `--taint-tainted-index-is-sink` is set.
+// compile-flags: --taint-tainted-index-is-sink
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let table = [1, 2, 3, 4];
+    let index = input();
+    let clean = table[2];
+    let value = table[index]; //~ ERROR tainted index used in bounds-checked access [T0006]
+    let _ = clean + value;
+}
+
+#[taint::source]
+fn input() -> usize {
+    7
+}
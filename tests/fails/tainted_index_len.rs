@@ -0,0 +1,20 @@
+// Test that only a tainted index is reported in bounds checks, not a tainted length.
+// compile-flags: --taint-tainted-index-is-sink
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let data = input();
+    let slice: &[u8] = &data;
+    let first = slice[0];
+
+    let table = [1, 2, 3, 4];
+    let value = table[data[1] as usize]; //~ ERROR tainted index used in bounds-checked access [T0006]
+    let _ = first + value;
+}
+
+#[taint::source]
+fn input() -> [u8; 4] {
+    [1, 2, 3, 4]
+}
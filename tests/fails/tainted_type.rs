@@ -0,0 +1,29 @@
+// Test that values of a type passed to `--taint-tainted-type` are tainted,
+// regardless of whether their fields are tainted.
+// compile-flags: --taint-tainted-type=UserInput
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+struct UserInput(i32);
+
+fn main() {
+    let constructed = UserInput(clean());
+    output(constructed.0); //~ ERROR function `output` received tainted input [T0001]
+
+    let returned = read();
+    output(returned.0); //~ ERROR function `output` received tainted input [T0001]
+}
+
+fn clean() -> i32 {
+    15
+}
+
+fn read() -> UserInput {
+    UserInput(15)
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
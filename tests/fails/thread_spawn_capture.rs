@@ -0,0 +1,32 @@
+// Test that a tainted value moved into a spawned thread is tainted within the thread.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::thread;
+
+fn main() {
+    let val = input();
+    let clean = 7;
+
+    let handle = thread::spawn(move || {
+        output(val); //~ ERROR function `output` received tainted input [T0001]
+    });
+    handle.join().unwrap();
+
+    let handle = thread::spawn(move || {
+        output(clean);
+    });
+    handle.join().unwrap();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,70 @@
+// Test that `?` passes the taint of its operand to the value it unwraps,
+// for user implementations of `Try` and for `ControlFlow`.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool, try_trait, control_flow_enum)]
+#![register_tool(taint)]
+
+use std::ops::{ControlFlow, Try};
+
+enum Checked {
+    Valid(i32),
+    Invalid(i32),
+}
+
+impl Try for Checked {
+    type Ok = i32;
+    type Error = i32;
+
+    fn into_result(self) -> Result<i32, i32> {
+        match self {
+            Checked::Valid(value) => Ok(value),
+            Checked::Invalid(value) => Err(value),
+        }
+    }
+
+    fn from_error(value: i32) -> Self {
+        Checked::Invalid(value)
+    }
+
+    fn from_ok(value: i32) -> Self {
+        Checked::Valid(value)
+    }
+}
+
+fn main() {
+    let _ = parse();
+    let _ = step();
+    let _ = clean();
+}
+
+fn parse() -> Checked {
+    let value = input()?;
+    output(value); //~ ERROR function `output` received tainted input [T0001]
+    Checked::Valid(0)
+}
+
+fn step() -> ControlFlow<(), i32> {
+    let value = ControlFlow::Continue(input_value())?;
+    output(value); //~ ERROR function `output` received tainted input [T0001]
+    ControlFlow::Continue(0)
+}
+
+fn clean() -> Checked {
+    let value = Checked::Valid(1)?;
+    output(value);
+    Checked::Valid(value)
+}
+
+#[taint::source]
+fn input() -> Checked {
+    Checked::Valid(1)
+}
+
+#[taint::source]
+fn input_value() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
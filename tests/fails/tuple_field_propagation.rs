@@ -0,0 +1,25 @@
+// Test that reading any field of a tainted tuple is tainted.
+// Since fields are not tracked separately, a call result taints the whole tuple,
+// and overwriting one of its fields does not clean the others.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let mut pair = input();
+    output(pair.0); //~ ERROR function `output` received tainted input [T0001]
+    output(pair.1); //~ ERROR function `output` received tainted input [T0001]
+
+    pair.0 = 1;
+    output(pair.1); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> (i32, i32) {
+    (4, 2)
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,51 @@
+// Test that the fields of a union share its taint, since they share its storage:
+// reading one field after writing a tainted value to another is tainted.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+#[derive(Clone, Copy)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+struct Packet {
+    bits: Bits,
+}
+
+fn main() {
+    let mut bits = Bits { float: 0.0 };
+    bits.int = input();
+    let read = unsafe { bits.float };
+    output(read as u32); //~ ERROR function `output` received tainted input [T0001]
+
+    let built = Bits { int: input() };
+    output(unsafe { built.float } as u32); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut behind = Bits { int: 0 };
+    write(&mut behind);
+    output(unsafe { behind.float } as u32); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut packet = Packet {
+        bits: Bits { float: 0.0 },
+    };
+    packet.bits.int = input();
+    output(unsafe { packet.bits.float } as u32); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut clean = Bits { int: 1 };
+    clean.float = 2.0;
+    output(unsafe { clean.int });
+}
+
+fn write(bits: &mut Bits) {
+    bits.int = input();
+}
+
+#[taint::source]
+fn input() -> u32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: u32) {}
@@ -0,0 +1,26 @@
+// Test that by default, calls to functions without MIR propagate the taint of their arguments.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+extern "C" {
+    fn transform(value: i32) -> i32;
+}
+
+fn main() {
+    let clean = unsafe { transform(1) };
+    output(clean);
+
+    let tainted = unsafe { transform(input()) };
+    output(tainted); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
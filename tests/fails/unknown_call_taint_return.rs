@@ -0,0 +1,20 @@
+// Test that calls to functions without MIR always return tainted values
+// with `--taint-unknown-call-policy=taint-return`.
+// compile-flags: --taint-unknown-call-policy=taint-return
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+extern "C" {
+    fn transform(value: i32) -> i32;
+}
+
+fn main() {
+    let value = unsafe { transform(1) };
+    output(value); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,32 @@
+// Test that the value returned by `unwrap_or` and its variants is tainted if the `Option` or `Result` is,
+// or if the default value or the closure which computes it is.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let tainted = Some(input());
+    output(tainted.unwrap_or(0)); //~ ERROR function `output` received tainted input [T0001]
+    output(tainted.unwrap_or_else(|| 0)); //~ ERROR function `output` received tainted input [T0001]
+    output(tainted.unwrap_or_default()); //~ ERROR function `output` received tainted input [T0001]
+
+    output(None.unwrap_or(input())); //~ ERROR function `output` received tainted input [T0001]
+    let secret = input();
+    output(None.unwrap_or_else(|| secret)); //~ ERROR function `output` received tainted input [T0001]
+
+    let parsed: Result<i32, ()> = Ok(input());
+    output(parsed.unwrap_or(0)); //~ ERROR function `output` received tainted input [T0001]
+    output(parsed.unwrap_or_else(|_| 0)); //~ ERROR function `output` received tainted input [T0001]
+
+    output(Some(1).unwrap_or(2));
+    output(Some(1).unwrap_or_else(|| 2));
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
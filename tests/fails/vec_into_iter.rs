@@ -0,0 +1,36 @@
+// Test that iterating over a tainted collection, such as with a `for` loop, yields tainted elements.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let values = vec![input(), 2];
+    for value in values {
+        output(value); //~ ERROR function `output` received tainted input [T0001]
+    }
+
+    let values = vec![input()];
+    for value in &values {
+        output(*value); //~ ERROR function `output` received tainted input [T0001]
+    }
+
+    let mut values = Vec::new();
+    values.push(input());
+    for value in values.into_iter() {
+        output(value); //~ ERROR function `output` received tainted input [T0001]
+    }
+
+    let clean = vec![1, 2];
+    for value in clean {
+        output(value);
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
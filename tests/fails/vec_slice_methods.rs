@@ -0,0 +1,24 @@
+// Test that the slice methods which a `Vec` dereferences to read the taint of the whole `Vec`.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let mut values = Vec::new();
+    values.push(input());
+    output(*values.first().unwrap()); //~ ERROR function `output` received tainted input [T0001]
+    output(*values.get(0).unwrap()); //~ ERROR function `output` received tainted input [T0001]
+    output(values[0]); //~ ERROR function `output` received tainted input [T0001]
+    for value in values.iter() {
+        output(*value); //~ ERROR function `output` received tainted input [T0001]
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    1
+}
+
+#[taint::sink]
+fn output(_: i32) {}
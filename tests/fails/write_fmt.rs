@@ -0,0 +1,46 @@
+// Test that `write!` taints the buffer it writes tainted values to,
+// and that writing them to a writer configured as a sink is reported.
+// compile-flags: --taint-output-sinks --taint-sink-writer=Socket
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+struct Socket;
+
+impl Write for Socket {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn main() {
+    let secret = input();
+
+    let mut buf = String::new();
+    write!(buf, "value: {}", secret).unwrap();
+    output(buf); //~ ERROR function `output` received tainted input [T0001]
+
+    let mut socket = Socket;
+    writeln!(socket, "{}", secret).unwrap(); //~ ERROR function `<Socket as std::io::Write>::write_fmt` received tainted input [T0001]
+    write!(io::stdout(), "{}", secret).unwrap(); //~ ERROR function `<std::io::Stdout as std::io::Write>::write_fmt` received tainted input [T0001]
+
+    let mut clean = String::new();
+    write!(clean, "{}", 1).unwrap();
+    output(clean);
+    writeln!(socket, "{}", 1).unwrap();
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: String) {}
@@ -0,0 +1,8 @@
+fn main() {}
+
+fn analyzed(a: i32, b: i32) -> (i32, i32) {
+    let c = a + 1;
+    let d = b;
+    let e = c;
+    (d, e)
+}
@@ -0,0 +1,8 @@
+fn main() {}
+
+fn analyzed(tainted: i32, a: i32, b: i32) -> (i32, i32, i32) {
+    let mixed = a * 2 + tainted;
+    let clean = a * 2 + 3;
+    let nested = (a + b) * (tainted - 1) / 2;
+    (mixed, clean, nested)
+}
@@ -0,0 +1,11 @@
+fn main() {}
+
+fn analyzed(a: i32, b: i32) -> i32 {
+    let mut c = b;
+    if b > 0 {
+        c = a;
+    } else {
+        c += 1;
+    }
+    c
+}
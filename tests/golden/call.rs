@@ -0,0 +1,16 @@
+fn main() {}
+
+fn identity(x: i32) -> i32 {
+    x
+}
+
+fn constant(_x: i32) -> i32 {
+    0
+}
+
+fn analyzed(a: i32, b: i32) -> i32 {
+    let c = identity(a);
+    let d = constant(a);
+    let e = identity(b);
+    c + d + e
+}
@@ -0,0 +1,9 @@
+fn main() {}
+
+fn consume(_s: String) {}
+
+fn analyzed(a: String) -> usize {
+    let b = a.clone();
+    consume(a);
+    b.len()
+}
@@ -0,0 +1,25 @@
+// Test that the captures of a closure are not tainted by the tainted arguments it is passed along with.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let value = input();
+    let clean = 1;
+    let log = move |_: i32| output(clean);
+    apply(log, value);
+}
+
+fn apply<F: Fn(i32)>(f: F, value: i32) {
+    f(value)
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,30 @@
+// Test that only the methods of `HashMap`, `BTreeMap` and `Vec` are modeled as collection methods,
+// and not other functions of the same name, and that the index passed to `Vec::insert` is not stored.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::collections::HashMap;
+
+fn main() {
+    let mut map = HashMap::new();
+    map.insert(1, input());
+    output(get(&map, 1));
+
+    let mut values = Vec::new();
+    values.insert(input() as usize, 1);
+    output(values.pop().unwrap());
+}
+
+// Does not read from the map, unlike `HashMap::get`.
+fn get(_map: &HashMap<i32, i32>, _key: i32) -> i32 {
+    0
+}
+
+#[taint::source]
+fn input() -> i32 {
+    0
+}
+
+#[taint::sink]
+fn output(_: i32) {}
@@ -0,0 +1,23 @@
+// Test that comparing a tainted value gives a clean result
+// when `--taint-clean-comparisons` is set.
+// compile-flags: --taint-clean-comparisons
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let x = 4;
+    output(x == secret);
+    output(secret < x);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: bool) {
+    ()
+}
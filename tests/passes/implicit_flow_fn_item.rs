@@ -0,0 +1,33 @@
+// Test that function items and closures without captures are clean in a tainted branch,
+// since their type alone determines their value.
+// compile-flags: --taint-implicit-flows
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    if secret > 0 {
+        let double = |x: i32| x * 2;
+        let f = three;
+        output(apply(double, 2) + apply(f, 0));
+    }
+}
+
+fn apply<F: Fn(i32) -> i32>(f: F, x: i32) -> i32 {
+    f(x)
+}
+
+fn three(_: i32) -> i32 {
+    3
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,33 @@
+// Test that the result of `matches!` on a tainted value is clean
+// when `--taint-clean-comparisons` is set.
+// compile-flags: --taint-clean-comparisons
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+enum Command {
+    Read,
+    Write(i32),
+}
+
+fn main() {
+    let command = input(false);
+    let is_write = matches!(command, Command::Write(_));
+    output(is_write);
+    let is_large = matches!(command, Command::Write(n) if n > 3);
+    output(is_large);
+}
+
+#[taint::source]
+fn input(read: bool) -> Command {
+    if read {
+        Command::Read
+    } else {
+        Command::Write(15)
+    }
+}
+
+#[taint::sink]
+fn output(_: bool) {
+    ()
+}
@@ -0,0 +1,21 @@
+// Test that the MIR location of a violation spans the sink call expression, rather than the whole statement,
+// when `--taint-mir-locations` is set.
+// compile-flags: --taint-mir-locations
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let _ignored = output(1, secret); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32, _: i32) -> i32 {
+    0
+}
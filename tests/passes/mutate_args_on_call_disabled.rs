@@ -0,0 +1,25 @@
+// Test that calls to functions without MIR do not taint their reference arguments
+// unless `--taint-mutate-args-on-call` is set.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+extern "C" {
+    fn fill(value: i32, buffer: &mut i32);
+}
+
+fn main() {
+    let mut buffer = 0;
+    unsafe { fill(input(), &mut buffer) };
+    output(buffer);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
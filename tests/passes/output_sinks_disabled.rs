@@ -0,0 +1,15 @@
+// Test that printing a tainted value is not reported by default.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    println!("{}", secret);
+    eprintln!("value: {}", secret);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
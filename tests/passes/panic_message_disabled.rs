@@ -0,0 +1,16 @@
+// Test that formatting a tainted value into a panic message is not reported by default.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    if secret > 100 {
+        panic!("invalid value {}", secret);
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
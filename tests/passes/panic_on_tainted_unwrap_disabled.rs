@@ -0,0 +1,14 @@
+// Test that unwrapping a tainted value is not reported by default.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let val = input();
+    let _ = val.unwrap();
+}
+
+#[taint::source]
+fn input() -> Option<i32> {
+    Some(15)
+}
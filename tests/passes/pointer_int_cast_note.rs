@@ -0,0 +1,22 @@
+// Test that casts of tainted values between pointers and integers are noted
+// when `--taint-note-pointer-casts` is set.
+// compile-flags: --taint-note-pointer-casts
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let address = input();
+    let pointer = address as *const u8;
+    consume(pointer as usize);
+
+    let clean = 0x2000 as *const u8;
+    consume(clean as usize);
+}
+
+fn consume(_: usize) {}
+
+#[taint::source]
+fn input() -> usize {
+    0x1000
+}
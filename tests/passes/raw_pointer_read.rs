@@ -0,0 +1,17 @@
+// Test that values read through raw pointers to clean data are clean
+// unless `--taint-unsafe-conservative` is set.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let value = 4;
+    let pointer = &value as *const i32;
+    let read = unsafe { *pointer };
+    output(read);
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
@@ -0,0 +1,21 @@
+// Test that constructs which the analysis does not handle are reported
+// when `--taint-report-unhandled` is set.
+// compile-flags: --taint-report-unhandled
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let buffer = [input(); 4];
+    output(buffer);
+}
+
+#[taint::source]
+fn input() -> u8 {
+    15
+}
+
+#[taint::sink]
+fn output(_: [u8; 4]) {
+    ()
+}
@@ -0,0 +1,23 @@
+// Test that public functions returning tainted data are reported when `--taint-return-escape` is set.
+// compile-flags: --taint-return-escape
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let _ = read_config();
+    let _ = default_config();
+}
+
+pub fn read_config() -> i32 { //~ WARN public function `read_config` may return tainted data [T0003]
+    input()
+}
+
+pub fn default_config() -> i32 {
+    15
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
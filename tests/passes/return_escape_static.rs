@@ -0,0 +1,24 @@
+// Test that a public function which returns a mutable static tainted elsewhere in the crate
+// is reported when `--taint-return-escape` is set.
+// compile-flags: --taint-return-escape
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+static mut STATE: i32 = 0;
+
+fn main() {
+    unsafe {
+        STATE = input();
+    }
+    let _ = cached();
+}
+
+pub fn cached() -> i32 { //~ WARN public function `cached` may return tainted data [T0003]
+    unsafe { STATE }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
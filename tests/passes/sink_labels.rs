@@ -0,0 +1,20 @@
+// Test that a tainted sink argument is labelled along with the source of its taint.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let secret = input();
+    let doubled = secret * 2;
+    output(1, doubled + 1); //~ ERROR function `output` received tainted input [T0001]
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32, _: i32) {
+    ()
+}
@@ -0,0 +1,25 @@
+// Test that the length of a tainted slice is clean without `--taint-len`,
+// so that branching on it in a slice pattern does not taint the values assigned in its arms.
+// compile-flags: --taint-implicit-flows
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let data = [input(), 2];
+    let slice: &[i32] = &data;
+    let count = match slice {
+        [_] => 1,
+        [_, _] => 2,
+        _ => 0,
+    };
+    output(count);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {}
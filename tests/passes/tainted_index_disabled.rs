@@ -0,0 +1,16 @@
+// Test that indexing with a tainted value is allowed
+// unless `--taint-tainted-index-is-sink` is set.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    let table = [1, 2, 3, 4];
+    let index = input();
+    let _ = table[index];
+}
+
+#[taint::source]
+fn input() -> usize {
+    7
+}
@@ -0,0 +1,28 @@
+// Test that constants passed to a sink in a branch on a tainted comparison are clean without `--taint-implicit-flows`,
+// unlike the result of `matches!`, which every branch assigns.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+fn main() {
+    if input() == 0 {
+        output(true);
+    }
+
+    let limit = input();
+    if limit > 3 {
+        let flag = true;
+        output(flag);
+    } else {
+        let other = false;
+        output(other);
+    }
+}
+
+#[taint::source]
+fn input() -> i32 {
+    0
+}
+
+#[taint::sink]
+fn output(_: bool) {}
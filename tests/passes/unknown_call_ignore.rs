@@ -0,0 +1,25 @@
+// Test that calls to functions without MIR return untainted values
+// with `--taint-unknown-call-policy=ignore`.
+// compile-flags: --taint-unknown-call-policy=ignore
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+extern "C" {
+    fn transform(value: i32) -> i32;
+}
+
+fn main() {
+    let value = unsafe { transform(input()) };
+    output(value);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    15
+}
+
+#[taint::sink]
+fn output(_: i32) {
+    ()
+}
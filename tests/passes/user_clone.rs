@@ -0,0 +1,48 @@
+// Test that implementations of `Clone`, `ToOwned` and `ToString` written by hand are analyzed,
+// rather than assumed to copy the taint of their receiver like those of the standard library.
+
+#![feature(register_tool)]
+#![register_tool(taint)]
+
+use std::borrow::Borrow;
+
+struct Fresh(i32);
+
+// Does not copy the value, unlike a derived implementation.
+impl Clone for Fresh {
+    fn clone(&self) -> Self {
+        Fresh(0)
+    }
+}
+
+struct Redacted(i32);
+
+impl Borrow<Redacted> for Fresh {
+    fn borrow(&self) -> &Redacted {
+        &Redacted(0)
+    }
+}
+
+impl ToOwned for Redacted {
+    type Owned = Fresh;
+
+    fn to_owned(&self) -> Fresh {
+        Fresh(0)
+    }
+}
+
+fn main() {
+    let fresh = Fresh(input()).clone();
+    output(fresh.0);
+
+    let owned = Redacted(input()).to_owned();
+    output(owned.0);
+}
+
+#[taint::source]
+fn input() -> i32 {
+    0
+}
+
+#[taint::sink]
+fn output(_: i32) {}